@@ -0,0 +1,617 @@
+use crate::configuration::{
+	load_configuration, FormatterConfiguration, IndentStyleConfiguration, LineEndingConfiguration,
+	LoadedConfiguration,
+};
+use crate::diff::unified_diff;
+use crate::editorconfig::{EditorConfigs, EDITORCONFIG_FILE};
+use crate::panic::catch_panic;
+use crate::watch::{FileWatcher, OwnWrites};
+use clap::ArgMatches;
+use colored::Colorize;
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+use rome_core::workspace::{FileSystem, OsFileSystem};
+use rome_core::App;
+use rome_formatter::{FormatError, FormatOptions, Formatted};
+use rome_path::{FileDiscovery, RomePath};
+use rslint_errors::file::SimpleFile;
+use rslint_errors::Emitter;
+use rslint_parser::ParserError;
+use std::collections::HashSet;
+use std::env;
+use std::ffi::OsStr;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// The outcome of the formatting of a single file
+enum FileStatus {
+	/// The formatted content has been written to the file
+	Formatted,
+	/// In check mode, the file is already formatted
+	AlreadyFormatted,
+	/// In check mode, the formatted content differs from the content of the file.
+	/// Holds the diff between the two contents when requested with `--diff`
+	NotFormatted(Option<String>),
+	Failed(Failure),
+}
+
+/// The reason why a file couldn't be formatted
+enum Failure {
+	/// The file couldn't be read or written, or the formatter returned an error
+	Format(FormatError),
+	/// The content contains syntax errors, holds the content that was parsed and its errors
+	SyntaxErrors(String, Vec<ParserError>),
+	/// The formatter panicked, holds the message of the panic
+	Panic(String),
+}
+
+impl From<FormatError> for Failure {
+	fn from(error: FormatError) -> Self {
+		Self::Format(error)
+	}
+}
+
+impl From<io::Error> for Failure {
+	fn from(error: io::Error) -> Self {
+		Self::Format(error.into())
+	}
+}
+
+/// Runs the `format` command: resolves the inputs to a list of files and formats
+/// each file that can be formatted, printing a summary at the end.
+///
+/// The files are formatted in parallel, using all the CPUs unless `--threads` is given.
+///
+/// With `--check`, the files are formatted in memory and never written. `--diff` implies `--check`
+/// and also prints what would change in each file that isn't formatted.
+///
+/// With `--watch`, the command keeps running after the first run and formats the files again
+/// each time they change.
+///
+/// With `--stdin-file-path`, the content read from the standard input is formatted instead and printed
+/// to the standard output.
+///
+/// The options are read from the `.editorconfig` files and from the closest `rome.json`, if any,
+/// which overrides them. The arguments override both. The files whose formatter is turned off
+/// by the configuration are skipped.
+/// The extensions and file names of the configuration are registered in the [App].
+/// The files listed in the `.romeignore` files, and optionally in the `.gitignore` files,
+/// are skipped while walking directories.
+///
+/// A file that can't be formatted, because it can't be read or written, because it contains syntax
+/// errors or because of a bug of the formatter, is reported as a diagnostic and the other files
+/// are still formatted. The files with syntax errors are formatted with `--format-with-errors`,
+/// the code that contains the errors being kept as it is.
+///
+/// Returns `false` if the CLI should exit with an error code: when the configuration is invalid,
+/// when an input doesn't match any file, when a file couldn't be formatted or, in check mode,
+/// when a file isn't formatted.
+pub(crate) fn run(app: &mut App, matches: &ArgMatches) -> bool {
+	let configuration = match load_configuration(&env::current_dir().unwrap_or_default()) {
+		Ok(configuration) => configuration,
+		Err(error) => {
+			error.emit(colored::control::SHOULD_COLORIZE.should_colorize());
+			return false;
+		}
+	};
+	let discovery = match configuration
+		.as_ref()
+		.map(LoadedConfiguration::file_discovery)
+	{
+		Some(Ok(discovery)) => discovery,
+		Some(Err(error)) => {
+			error.emit(colored::control::SHOULD_COLORIZE.should_colorize());
+			return false;
+		}
+		None => FileDiscovery::default(),
+	};
+	if let Some(Err(error)) = configuration
+		.as_ref()
+		.map(|loaded| loaded.register_handlers(app))
+	{
+		error.emit(colored::control::SHOULD_COLORIZE.should_colorize());
+		return false;
+	}
+	let app: &App = app;
+	let discovery = if matches.is_present("use_gitignore") {
+		discovery.with_gitignore(true)
+	} else {
+		discovery
+	};
+	let arguments = formatter_configuration_from_matches(matches);
+	let editorconfigs = EditorConfigs::default();
+	let file_system = OsFileSystem;
+
+	if let Some(path) = matches.value_of("stdin_file_path") {
+		return format_stdin(
+			app,
+			path,
+			configuration.as_ref(),
+			&editorconfigs,
+			&arguments,
+		);
+	}
+	let diff = matches.is_present("diff");
+	let check = diff || matches.is_present("check");
+
+	let inputs: Vec<&str> = matches.values_of("input").unwrap().collect();
+	let discovered = discovery.discover(&inputs);
+
+	for input in &discovered.unmatched {
+		eprintln!("No files found matching \"{}\"", input);
+	}
+
+	let pool = match thread_pool(matches) {
+		Ok(pool) => pool,
+		Err(error) => {
+			eprintln!(
+				"Failed to start the threads that format the files: {}",
+				error
+			);
+			return false;
+		}
+	};
+
+	// the order of the results is the order of the files, regardless of the thread that
+	// processed each file, so that the output is always the same
+	let outcomes: Vec<Option<(PathBuf, FileStatus)>> = pool.install(|| {
+		discovered
+			.files
+			.par_iter()
+			.map(|path| {
+				let mut file = deduce_handler(app, &file_system, path);
+
+				if !can_format(&file) || is_configuration_file(&file, configuration.as_ref()) {
+					return None;
+				}
+
+				let options =
+					format_options(&file, configuration.as_ref(), &editorconfigs, &arguments)?;
+				let result = catch_panic(|| {
+					if check {
+						check_file(app, &file_system, &file, options, diff)
+					} else {
+						format_file(app, &file_system, &mut file, options)
+					}
+				});
+
+				let status = match result {
+					Ok(Ok(status)) => status,
+					Ok(Err(failure)) => FileStatus::Failed(failure),
+					Err(message) => FileStatus::Failed(Failure::Panic(message)),
+				};
+				Some((path.clone(), status))
+			})
+			.collect()
+	});
+
+	let skipped = outcomes.iter().filter(|outcome| outcome.is_none()).count();
+	let results: Vec<(PathBuf, FileStatus)> = outcomes.into_iter().flatten().collect();
+
+	let summary = if check {
+		print_check_summary(&results, skipped)
+	} else {
+		print_summary(&results, skipped)
+	};
+
+	if matches.is_present("watch") {
+		return watch_inputs(
+			app,
+			&file_system,
+			&discovery,
+			&inputs,
+			configuration.as_ref(),
+			&editorconfigs,
+			&arguments,
+		);
+	}
+
+	discovered.unmatched.is_empty() && summary
+}
+
+/// Formats the files of the inputs each time they change, printing a status line for each file,
+/// until the process is interrupted.
+///
+/// The inputs are resolved again after each change, so that the new files are formatted and the
+/// ignored files are skipped like in the first run. The notifications of the writes of the
+/// formatter itself are ignored.
+///
+/// Returns `false` if the files can't be watched.
+fn watch_inputs(
+	app: &App,
+	file_system: &dyn FileSystem,
+	discovery: &FileDiscovery,
+	inputs: &[&str],
+	configuration: Option<&LoadedConfiguration>,
+	editorconfigs: &EditorConfigs,
+	arguments: &FormatterConfiguration,
+) -> bool {
+	let current_dir = env::current_dir().unwrap_or_default();
+	// a glob pattern doesn't exist on disk, its files are found in the current directory
+	let watched: Vec<PathBuf> = inputs
+		.iter()
+		.map(|input| match Path::new(input) {
+			path if path.exists() => current_dir.join(path),
+			_ => current_dir.clone(),
+		})
+		.collect();
+	let watcher = match FileWatcher::new(&watched) {
+		Ok(watcher) => watcher,
+		Err(error) => {
+			eprintln!("Failed to watch the files: {}", error);
+			return false;
+		}
+	};
+	let mut own_writes = OwnWrites::default();
+
+	println!("Watching for changes, press Ctrl+C to stop");
+	watcher.run(|changed| {
+		let discovered = discovery.discover(inputs);
+		let files: HashSet<PathBuf> = discovered
+			.files
+			.iter()
+			.map(|path| current_dir.join(path))
+			.collect();
+
+		for path in changed.iter().filter(|path| files.contains(*path)) {
+			let path = path.strip_prefix(&current_dir).unwrap_or(path);
+			let mut file = deduce_handler(app, file_system, path);
+			if !can_format(&file) || is_configuration_file(&file, configuration) {
+				continue;
+			}
+			let options = match format_options(&file, configuration, editorconfigs, arguments) {
+				Some(options) => options,
+				None => continue,
+			};
+
+			let result = catch_panic(|| {
+				reformat_file(app, file_system, &mut file, options, &mut own_writes)
+			});
+			match result {
+				Ok(Ok(Some(FileStatus::AlreadyFormatted))) => {
+					println!("Already formatted {}", path.display())
+				}
+				Ok(Ok(Some(_))) => println!("Formatted {}", path.display()),
+				Ok(Ok(None)) => {}
+				Ok(Err(failure)) => emit_failure(path, &failure),
+				Err(message) => emit_failure(path, &Failure::Panic(message)),
+			}
+		}
+	});
+	true
+}
+
+/// Returns the file with its handler, deduced from its name or, for the files without an
+/// extension, from its content
+fn deduce_handler<'app>(
+	app: &'app App,
+	file_system: &dyn FileSystem,
+	path: &Path,
+) -> RomePath<'app> {
+	let file = RomePath::new(path).deduce_handler(app);
+	if file.get_handler().is_some() {
+		return file;
+	}
+
+	match file.read(file_system) {
+		Ok(content) => file.deduce_handler_from_content(app, &content),
+		// the error is reported if the file turns out to be formatted
+		Err(_) => file,
+	}
+}
+
+/// Creates the pool of threads that format the files, with as many threads as `--threads`
+/// or, by default, as many threads as CPUs
+fn thread_pool(matches: &ArgMatches) -> Result<ThreadPool, ThreadPoolBuildError> {
+	let threads = matches
+		.value_of("threads")
+		.and_then(|threads| threads.parse().ok())
+		.unwrap_or(0);
+	ThreadPoolBuilder::new().num_threads(threads).build()
+}
+
+/// Returns the options used to format the file: the options of its `.editorconfig` files,
+/// overridden by the options of its language and of its path in the configuration, overridden
+/// by the arguments.
+///
+/// Returns [None] if the formatting of the file is turned off by the configuration.
+fn format_options(
+	file: &RomePath,
+	configuration: Option<&LoadedConfiguration>,
+	editorconfigs: &EditorConfigs,
+	arguments: &FormatterConfiguration,
+) -> Option<FormatOptions> {
+	let mut formatter = editorconfigs.formatter_for(file);
+	if let (Some(loaded), Some(handler)) = (configuration, file.get_handler()) {
+		formatter.merge(&loaded.formatter_for(file, &handler.language()));
+	}
+	formatter.merge(arguments);
+	formatter
+		.is_enabled()
+		.then(|| formatter.to_format_options())
+}
+
+/// Returns `true` if the file is the configuration file, which is never formatted
+/// because the JSON formatter would remove its comments, or an `.editorconfig` file,
+/// whose sections could be mistaken for JSON arrays
+fn is_configuration_file(file: &RomePath, configuration: Option<&LoadedConfiguration>) -> bool {
+	if file.file_name() == Some(OsStr::new(EDITORCONFIG_FILE)) {
+		return true;
+	}
+
+	let loaded = match configuration {
+		Some(loaded) => loaded,
+		None => return false,
+	};
+	matches!(env::current_dir(), Ok(current_dir) if current_dir.join(file.as_path()) == loaded.path)
+}
+
+/// Returns `true` if the handler of the file has the format capability
+fn can_format(file: &RomePath) -> bool {
+	matches!(file.get_handler(), Some(handler) if handler.capabilities().format)
+}
+
+/// Formats the content of the standard input and prints the result to the standard output.
+///
+/// The path is only used to find the handler of the content, the file doesn't need to exist.
+/// Without an extension, the handler is deduced from the content.
+fn format_stdin(
+	app: &App,
+	path: &str,
+	configuration: Option<&LoadedConfiguration>,
+	editorconfigs: &EditorConfigs,
+	arguments: &FormatterConfiguration,
+) -> bool {
+	let mut content = String::new();
+	if let Err(error) = io::stdin().read_to_string(&mut content) {
+		emit_failure(Path::new(path), &Failure::Format(error.into()));
+		return false;
+	}
+
+	let file = RomePath::new(path)
+		.deduce_handler(app)
+		.deduce_handler_from_content(app, &content);
+	let path = Path::new(path);
+	if !can_format(&file) {
+		emit_failure(path, &Failure::Format(FormatError::UnsupportedLanguage));
+		return false;
+	}
+	// the content of a file that isn't formatted is printed as is, like it would be left untouched
+	let options = match format_options(&file, configuration, editorconfigs, arguments) {
+		Some(options) => options,
+		None => {
+			print!("{}", content);
+			return true;
+		}
+	};
+
+	let result = catch_panic(|| format_content(app, &file, &content, options));
+	match result {
+		Ok(Ok(formatted)) => {
+			print!("{}", formatted.code());
+			true
+		}
+		Ok(Err(failure)) => {
+			emit_failure(path, &failure);
+			false
+		}
+		Err(message) => {
+			emit_failure(path, &Failure::Panic(message));
+			false
+		}
+	}
+}
+
+/// Formats the file and writes the result back to it.
+///
+/// The file is left untouched if the formatting fails.
+fn format_file(
+	app: &App,
+	file_system: &dyn FileSystem,
+	file: &mut RomePath,
+	options: FormatOptions,
+) -> Result<FileStatus, Failure> {
+	let content = file.read(file_system)?;
+
+	let formatted = format_content(app, file, &content, options)?;
+	file.save(file_system, formatted.code())?;
+	Ok(FileStatus::Formatted)
+}
+
+/// Formats a file that changed while watching, and writes the result back to it if it differs.
+///
+/// Returns [None] if the content of the file is the one written by the previous formatting.
+fn reformat_file(
+	app: &App,
+	file_system: &dyn FileSystem,
+	file: &mut RomePath,
+	options: FormatOptions,
+	own_writes: &mut OwnWrites,
+) -> Result<Option<FileStatus>, Failure> {
+	let content = file.read(file_system)?;
+	if own_writes.is_own_write(file, &content) {
+		return Ok(None);
+	}
+
+	let formatted = format_content(app, file, &content, options)?;
+	if formatted.code() == &content {
+		return Ok(Some(FileStatus::AlreadyFormatted));
+	}
+	file.save(file_system, formatted.code())?;
+	own_writes.record(file, formatted.code());
+	Ok(Some(FileStatus::Formatted))
+}
+
+/// Formats the file in memory and compares the result with the content of the file
+fn check_file(
+	app: &App,
+	file_system: &dyn FileSystem,
+	file: &RomePath,
+	options: FormatOptions,
+	diff: bool,
+) -> Result<FileStatus, Failure> {
+	let content = file.read(file_system)?;
+
+	let formatted = format_content(app, file, &content, options)?;
+	if formatted.code() == &content {
+		Ok(FileStatus::AlreadyFormatted)
+	} else if diff {
+		let path = file.display().to_string();
+		Ok(FileStatus::NotFormatted(Some(unified_diff(
+			&path,
+			&content,
+			formatted.code(),
+		))))
+	} else {
+		Ok(FileStatus::NotFormatted(None))
+	}
+}
+
+/// Formats the content of the file, the syntax errors are returned with the content
+fn format_content(
+	app: &App,
+	path: &Path,
+	content: &str,
+	options: FormatOptions,
+) -> Result<Formatted, Failure> {
+	app.format(path, content, options)
+		.map_err(|error| match error {
+			FormatError::SyntaxErrors(errors) => Failure::SyntaxErrors(content.to_string(), errors),
+			error => Failure::Format(error),
+		})
+}
+
+/// Reads the formatter options passed as arguments, `--indent-style`, `--indent-size`,
+/// `--line-width`, `--line-ending` and `--format-with-errors`
+fn formatter_configuration_from_matches(matches: &ArgMatches) -> FormatterConfiguration {
+	let indent_style = matches.value_of("indent_style").map(|style| match style {
+		"space" => IndentStyleConfiguration::Space,
+		_ => IndentStyleConfiguration::Tab,
+	});
+
+	FormatterConfiguration {
+		enabled: None,
+		indent_style,
+		indent_size: matches
+			.value_of("indent_size")
+			.and_then(|size| size.parse().ok()),
+		line_width: matches
+			.value_of("line_width")
+			.and_then(|width| width.parse().ok()),
+		line_ending: matches
+			.value_of("line_ending")
+			.and_then(|line_ending| match line_ending {
+				"lf" => Some(LineEndingConfiguration::Lf),
+				"crlf" => Some(LineEndingConfiguration::Crlf),
+				"cr" => Some(LineEndingConfiguration::Cr),
+				"auto" => Some(LineEndingConfiguration::Auto),
+				_ => None,
+			}),
+		format_with_errors: if matches.is_present("format_with_errors") {
+			Some(true)
+		} else {
+			None
+		},
+	}
+}
+
+/// Prints the failure to the standard error, as an error line that names the file.
+///
+/// The syntax errors of a file are printed before, as diagnostics on the content that was parsed.
+fn emit_failure(path: &Path, failure: &Failure) {
+	if let Failure::SyntaxErrors(source, errors) = failure {
+		// the positions of the errors don't count the byte order mark, which isn't parsed
+		let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+		let file = SimpleFile::new(path.display().to_string(), source.to_string());
+		let mut emitter = Emitter::new(&file);
+		let color = colored::control::SHOULD_COLORIZE.should_colorize();
+		for error in errors {
+			emitter
+				.emit_stderr(error, color)
+				.expect("failed to emit the diagnostic");
+		}
+	}
+
+	let (code, message) = match failure {
+		Failure::Format(error @ FormatError::Io(_)) => ("IO", error.to_string()),
+		Failure::Format(error) => ("Format", error.to_string()),
+		Failure::SyntaxErrors(_, errors) => (
+			"Format",
+			format!("the file contains {} syntax error(s)", errors.len()),
+		),
+		Failure::Panic(message) => ("Panic", format!("the formatter panicked: {}", message)),
+	};
+
+	let help = match failure {
+		Failure::SyntaxErrors(..) => {
+			Some("fix the errors, or use --format-with-errors to format the rest of the file")
+		}
+		Failure::Panic(_) => Some("this is a bug of the formatter, please report it"),
+		Failure::Format(_) => None,
+	};
+
+	let header = format!("error[{}]", code).red().bold();
+	eprintln!("{}: {}: {}", header, path.display(), message);
+	if let Some(help) = help {
+		eprintln!("  {} {}", "= help:".bold(), help);
+	}
+}
+
+/// Prints the outcome of every file and returns `false` if any file failed
+fn print_summary(results: &[(PathBuf, FileStatus)], skipped: usize) -> bool {
+	let mut formatted = 0;
+	let mut failed = 0;
+
+	for (path, status) in results {
+		match status {
+			FileStatus::Failed(failure) => {
+				failed += 1;
+				emit_failure(path, failure);
+			}
+			_ => {
+				formatted += 1;
+				println!("Formatted {}", path.display());
+			}
+		}
+	}
+
+	println!(
+		"Formatted {} file(s), {} failed, {} skipped",
+		formatted, failed, skipped
+	);
+
+	failed == 0
+}
+
+/// Prints the files that aren't formatted or that failed and returns `false` if there's any
+fn print_check_summary(results: &[(PathBuf, FileStatus)], skipped: usize) -> bool {
+	let mut not_formatted = 0;
+	let mut failed = 0;
+
+	for (path, status) in results {
+		match status {
+			FileStatus::NotFormatted(diff) => {
+				not_formatted += 1;
+				match diff {
+					Some(diff) => print!("{}", diff),
+					None => println!("Not formatted {}", path.display()),
+				}
+			}
+			FileStatus::Failed(failure) => {
+				failed += 1;
+				emit_failure(path, failure);
+			}
+			_ => {}
+		}
+	}
+
+	println!(
+		"Checked {} file(s), {} not formatted, {} failed, {} skipped",
+		results.len(),
+		not_formatted,
+		failed,
+		skipped
+	);
+
+	not_formatted == 0 && failed == 0
+}
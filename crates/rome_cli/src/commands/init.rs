@@ -0,0 +1,114 @@
+use crate::configuration::{CONFIGURATION_FILE, DEFAULT_INDENT_SIZE};
+use clap::ArgMatches;
+use rome_formatter::{FormatOptions, IndentStyle, LineEndingStyle};
+use std::fs;
+use std::path::Path;
+
+/// Runs the `init` command: writes a `rome.json` with the default options in the current
+/// directory and prints the next steps.
+///
+/// Returns `false` if the file already exists and `--force` isn't passed, or if it can't be written.
+pub(crate) fn run(matches: &ArgMatches) -> bool {
+	let path = Path::new(CONFIGURATION_FILE);
+
+	if path.exists() && !matches.is_present("force") {
+		eprintln!(
+			"The file \"{}\" already exists, use --force to overwrite it",
+			path.display()
+		);
+		return false;
+	}
+
+	if let Err(error) = fs::write(path, default_configuration()) {
+		eprintln!("Failed to write \"{}\": {}", path.display(), error);
+		return false;
+	}
+
+	println!("Created \"{}\"", path.display());
+	println!();
+	println!("Next steps:");
+	println!("  1. Review the options in \"{}\"", path.display());
+	println!("  2. List the paths to skip in a \".romeignore\" file, like a \".gitignore\"");
+	println!("  3. Run `rome format --check .` to find the files that aren't formatted");
+	println!("  4. Run `rome format .` to format them");
+
+	true
+}
+
+/// Returns the content of the configuration file, with the default value of every option
+fn default_configuration() -> String {
+	let options = FormatOptions::default();
+	let (indent_style, indent_size) = match IndentStyle::default() {
+		IndentStyle::Tab => ("tab", DEFAULT_INDENT_SIZE),
+		IndentStyle::Space(size) => ("space", size),
+	};
+	let line_ending = match options.line_ending {
+		LineEndingStyle::Lf => "lf",
+		LineEndingStyle::Crlf => "crlf",
+		LineEndingStyle::Cr => "cr",
+		LineEndingStyle::Auto => "auto",
+	};
+
+	format!(
+		r#"{{
+	// The options of the formatter, for every language.
+	// They can be overridden for a language inside "javascript" and "json",
+	// and for some paths inside "overrides"
+	"formatter": {{
+		// Whether the files are formatted
+		"enabled": true,
+		// "tab" or "space"
+		"indentStyle": "{}",
+		// The number of spaces of an indent, when "indentStyle" is "space"
+		"indentSize": {},
+		// The max width of a line
+		"lineWidth": {},
+		// "lf", "crlf", "cr" or "auto" to keep the line ending of each file
+		"lineEnding": "{}",
+		// Whether the files with syntax errors are formatted, the invalid code being kept as it is
+		"formatWithErrors": {}
+	}},
+	"javascript": {{
+		"formatter": {{}}
+	}},
+	"json": {{
+		"formatter": {{}}
+	}},
+	"files": {{
+		// Glob patterns of the files to format when walking directories, all of them if empty
+		"include": [],
+		// Glob patterns of the files to skip, in addition to the ones of ".romeignore"
+		"ignore": [],
+		// Whether the files listed in ".gitignore" are skipped too
+		"useGitignore": false,
+		// Extensions handled like a built-in extension, e.g. {{ "es6": "js" }}
+		"extensions": {{}},
+		// File names handled like a built-in extension, e.g. {{ ".babelrc": "json" }}
+		"fileNames": {{}}
+	}},
+	// The options of the files matching glob patterns, applied in order, e.g.
+	// {{ "include": ["src/generated/**"], "formatter": {{ "lineWidth": 120 }} }}
+	"overrides": []
+}}
+"#,
+		indent_style, indent_size, options.line_width, line_ending, options.format_with_errors
+	)
+}
+
+#[cfg(test)]
+mod test {
+	use super::default_configuration;
+	use crate::configuration::parse_configuration;
+
+	#[test]
+	fn default_configuration_is_valid() {
+		let configuration = parse_configuration(&default_configuration()).unwrap();
+		let options = configuration.formatter.to_format_options();
+		let default = rome_formatter::FormatOptions::default();
+
+		assert_eq!(options.indent_style, default.indent_style);
+		assert_eq!(options.line_width, default.line_width);
+		assert_eq!(options.line_ending, default.line_ending);
+		assert_eq!(options.format_with_errors, default.format_with_errors);
+	}
+}
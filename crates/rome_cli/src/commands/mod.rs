@@ -0,0 +1,4 @@
+//! The implementation of the subcommands exposed by the CLI
+pub(crate) mod format;
+pub(crate) mod init;
+pub(crate) mod parse;
@@ -0,0 +1,86 @@
+use clap::ArgMatches;
+use rome_core::file_handlers::SourceType;
+use rome_core::workspace::{FileSystem, OsFileSystem};
+use rome_core::App;
+use rome_path::RomePath;
+use rslint_errors::file::SimpleFile;
+use rslint_errors::{Diagnostic, Emitter, Severity};
+use rslint_parser::{parse, tokenize, FileKind, Syntax};
+use std::path::Path;
+
+/// Runs the `parse` command: prints the lossless syntax tree of a file, with the kind, range and
+/// trivia of every node and token, or its tokens with `--tokens`. The diagnostics of the parser
+/// are printed to the standard error.
+///
+/// The file is parsed as a script, a module or TypeScript according to `--script`, `--module` or
+/// `--typescript`, or else according to the handler of its extension.
+///
+/// Returns `false` if the file can't be read or if the parser reported any error.
+pub(crate) fn run(app: &App, matches: &ArgMatches) -> bool {
+	let path = matches.value_of("input").unwrap();
+	let text = match OsFileSystem.read(Path::new(path)) {
+		Ok(text) => text,
+		Err(error) => {
+			eprintln!("Failed to read \"{}\": {}", path, error);
+			return false;
+		}
+	};
+
+	let diagnostics = if matches.is_present("tokens") {
+		let (tokens, errors) = tokenize(&text, 0);
+		let mut start = 0;
+		for token in tokens {
+			let end = start + token.len;
+			println!(
+				"{:?}@{}..{} {:?}",
+				token.kind,
+				start,
+				end,
+				&text[start..end]
+			);
+			start = end;
+		}
+		errors
+	} else {
+		let parse = parse(&text, 0, syntax_from_matches(app, matches, path));
+		print!("{:#?}", parse.syntax());
+		parse.errors().to_vec()
+	};
+
+	let file = SimpleFile::new(path.to_string(), text);
+	emit_diagnostics(&file, &diagnostics);
+
+	!diagnostics
+		.iter()
+		.any(|diagnostic| matches!(diagnostic.severity, Severity::Error | Severity::Bug))
+}
+
+/// Creates the [Syntax] from the `--module`, `--script` and `--typescript` arguments, falling back
+/// to the source type of the handler of the file
+fn syntax_from_matches(app: &App, matches: &ArgMatches, path: &str) -> Syntax {
+	let file_kind = if matches.is_present("typescript") {
+		FileKind::TypeScript
+	} else if matches.is_present("module") {
+		FileKind::Module
+	} else if matches.is_present("script") {
+		FileKind::Script
+	} else {
+		let file = RomePath::new(path).deduce_handler(app);
+		return file
+			.get_handler()
+			.and_then(|handler| handler.source_type())
+			.map_or_else(Syntax::default, SourceType::syntax);
+	};
+
+	Syntax::new(file_kind)
+}
+
+fn emit_diagnostics(file: &SimpleFile, diagnostics: &[Diagnostic]) {
+	let color = colored::control::SHOULD_COLORIZE.should_colorize();
+	let mut emitter = Emitter::new(file);
+	for diagnostic in diagnostics {
+		emitter
+			.emit_stderr(diagnostic, color)
+			.expect("failed to emit the diagnostic");
+	}
+}
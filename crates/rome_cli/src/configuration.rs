@@ -0,0 +1,733 @@
+//! The project configuration, read from a `rome.json` file.
+//!
+//! The file is searched in the current directory and then in each of its ancestors:
+//!
+//! ```json
+//! {
+//!     "formatter": { "indentStyle": "space", "indentSize": 4, "lineWidth": 100, "lineEnding": "auto" },
+//!     "files": { "include": ["src/**"], "ignore": ["src/generated/**"], "useGitignore": true },
+//!     "json": { "formatter": { "indentStyle": "tab" } },
+//!     "overrides": [{ "include": ["tests/fixtures/**"], "formatter": { "enabled": false } }]
+//! }
+//! ```
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use rome_core::file_handlers::{ExtensionHandler, Language};
+use rome_core::App;
+use rome_formatter::{FormatOptions, IndentStyle, LineEndingStyle};
+use rome_path::FileDiscovery;
+use rslint_errors::file::{Files, SimpleFile};
+use rslint_errors::{Diagnostic, Emitter};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// The name of the configuration file
+pub(crate) const CONFIGURATION_FILE: &str = "rome.json";
+
+/// The indent size used when the indent style is `space` and no size is given
+pub(crate) const DEFAULT_INDENT_SIZE: u8 = 2;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub(crate) struct Configuration {
+	/// The formatter options of every language
+	pub formatter: FormatterConfiguration,
+
+	/// The files processed when walking directories
+	pub files: FilesConfiguration,
+
+	/// The options of JavaScript files, which override the global options
+	pub javascript: LanguageConfiguration,
+
+	/// The options of JSON files, which override the global options
+	pub json: LanguageConfiguration,
+
+	/// The options of the files matching glob patterns, which override the options of their
+	/// language. The matching overrides are applied in order
+	pub overrides: Vec<OverrideConfiguration>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub(crate) struct FormatterConfiguration {
+	/// Whether the files are formatted, `true` by default
+	pub enabled: Option<bool>,
+
+	pub indent_style: Option<IndentStyleConfiguration>,
+
+	/// The size of the indent, only used when the indent style is `space`
+	pub indent_size: Option<u8>,
+
+	pub line_width: Option<u16>,
+
+	pub line_ending: Option<LineEndingConfiguration>,
+
+	/// Whether the files with syntax errors are formatted, `false` by default
+	pub format_with_errors: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum IndentStyleConfiguration {
+	Tab,
+	Space,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum LineEndingConfiguration {
+	Lf,
+	Crlf,
+	Cr,
+	/// The line ending of the first line of each file
+	Auto,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub(crate) struct FilesConfiguration {
+	/// Glob patterns of the files to process. All the files are processed when empty
+	pub include: Vec<String>,
+
+	/// Glob patterns of the files to skip
+	pub ignore: Vec<String>,
+
+	/// Whether the files listed in the `.gitignore` files are skipped
+	pub use_gitignore: bool,
+
+	/// Extensions handled like a built-in extension, e.g. `{ "es6": "js" }`
+	pub extensions: BTreeMap<String, String>,
+
+	/// File names handled like a built-in extension, e.g. `{ ".babelrc": "json" }`
+	pub file_names: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct LanguageConfiguration {
+	pub formatter: FormatterConfiguration,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct OverrideConfiguration {
+	/// Glob patterns of the files, relative to the directory of the configuration file
+	pub include: Vec<String>,
+
+	pub formatter: FormatterConfiguration,
+}
+
+impl FormatterConfiguration {
+	/// Overrides the options of `self` with the options that are set in `other`
+	pub fn merge(&mut self, other: &FormatterConfiguration) {
+		if other.enabled.is_some() {
+			self.enabled = other.enabled;
+		}
+		if other.indent_style.is_some() {
+			self.indent_style = other.indent_style;
+		}
+		if other.indent_size.is_some() {
+			self.indent_size = other.indent_size;
+		}
+		if other.line_width.is_some() {
+			self.line_width = other.line_width;
+		}
+		if other.line_ending.is_some() {
+			self.line_ending = other.line_ending;
+		}
+		if other.format_with_errors.is_some() {
+			self.format_with_errors = other.format_with_errors;
+		}
+	}
+
+	/// Returns `false` if the formatting of the files is turned off
+	pub fn is_enabled(&self) -> bool {
+		self.enabled.unwrap_or(true)
+	}
+
+	/// Creates the [FormatOptions], using the default value of the options that aren't set
+	pub fn to_format_options(&self) -> FormatOptions {
+		let mut options = FormatOptions::default();
+
+		match self.indent_style {
+			Some(IndentStyleConfiguration::Tab) => options.indent_style = IndentStyle::Tab,
+			Some(IndentStyleConfiguration::Space) => {
+				options.indent_style =
+					IndentStyle::Space(self.indent_size.unwrap_or(DEFAULT_INDENT_SIZE))
+			}
+			None => {}
+		}
+		if let Some(line_width) = self.line_width {
+			options.line_width = line_width;
+		}
+		if let Some(line_ending) = self.line_ending {
+			options.line_ending = match line_ending {
+				LineEndingConfiguration::Lf => LineEndingStyle::Lf,
+				LineEndingConfiguration::Crlf => LineEndingStyle::Crlf,
+				LineEndingConfiguration::Cr => LineEndingStyle::Cr,
+				LineEndingConfiguration::Auto => LineEndingStyle::Auto,
+			};
+		}
+		if let Some(format_with_errors) = self.format_with_errors {
+			options.format_with_errors = format_with_errors;
+		}
+
+		options
+	}
+}
+
+impl Configuration {
+	/// Returns the formatter options of the language, merged with the global ones
+	pub fn formatter_for(&self, language: &Language) -> FormatterConfiguration {
+		let mut formatter = self.formatter.clone();
+		match language {
+			Language::Js | Language::Ts => formatter.merge(&self.javascript.formatter),
+			Language::Json => formatter.merge(&self.json.formatter),
+			Language::Unknown => {}
+		}
+		formatter
+	}
+}
+
+/// A configuration file found on disk
+pub(crate) struct LoadedConfiguration {
+	/// The path of the configuration file
+	pub path: PathBuf,
+
+	/// The directory that contains the configuration file
+	pub root: PathBuf,
+
+	pub configuration: Configuration,
+
+	/// The compiled patterns of each override of the configuration
+	overrides: Vec<GlobSet>,
+
+	file: SimpleFile,
+}
+
+impl LoadedConfiguration {
+	/// Returns the formatter options of the file: the options of its language, overridden
+	/// by the overrides whose patterns match its path
+	pub fn formatter_for(&self, path: &Path, language: &Language) -> FormatterConfiguration {
+		let mut formatter = self.configuration.formatter_for(language);
+
+		let path = match env::current_dir() {
+			Ok(current_dir) => current_dir.join(path),
+			Err(_) => path.to_path_buf(),
+		};
+		let relative_path = match path.strip_prefix(&self.root) {
+			Ok(relative_path) => relative_path,
+			Err(_) => return formatter,
+		};
+
+		for (configuration, globs) in self.configuration.overrides.iter().zip(&self.overrides) {
+			if globs.is_match(relative_path) {
+				formatter.merge(&configuration.formatter);
+			}
+		}
+		formatter
+	}
+
+	/// Creates the [FileDiscovery] that applies the include and ignore patterns
+	/// of the configuration, relative to its directory
+	pub fn file_discovery(&self) -> Result<FileDiscovery, ConfigurationError> {
+		let files = &self.configuration.files;
+		let glob_error = |list, error: globset::Error| {
+			self.error_at_string(
+				&[JsonKey::Field("files"), JsonKey::Field(list)],
+				error.glob().unwrap_or_default(),
+				"invalid glob pattern",
+				error.kind().to_string(),
+			)
+		};
+		FileDiscovery::default()
+			.with_root(&self.root)
+			.with_gitignore(files.use_gitignore)
+			.with_include(&files.include)
+			.map_err(|error| glob_error("include", error))?
+			.with_ignore(&files.ignore)
+			.map_err(|error| glob_error("ignore", error))
+	}
+
+	/// Registers the handlers of the extensions and file names of the configuration,
+	/// which reuse the handler of a built-in extension
+	pub fn register_handlers(&self, app: &mut App) -> Result<(), ConfigurationError> {
+		let files = &self.configuration.files;
+		for (extension, target) in &files.extensions {
+			let handler = self.extension_handler(app, "extensions", target)?;
+			app.register_extension(extension.as_str(), handler);
+		}
+		for (file_name, target) in &files.file_names {
+			let handler = self.extension_handler(app, "fileNames", target)?;
+			app.register_file_name(file_name.as_str(), handler);
+		}
+		Ok(())
+	}
+
+	/// Returns the handler of the extension given in the `files` field `map`
+	fn extension_handler(
+		&self,
+		app: &App,
+		map: &str,
+		extension: &str,
+	) -> Result<Arc<dyn ExtensionHandler>, ConfigurationError> {
+		app.extension_handler(extension).ok_or_else(|| {
+			self.error_at_string(
+				&[JsonKey::Field("files"), JsonKey::Field(map)],
+				extension,
+				"unknown extension",
+				format!(
+					"no handler is registered for the extension \"{}\"",
+					extension
+				),
+			)
+		})
+	}
+
+	/// Compiles the patterns of the overrides
+	fn compile_overrides(&self) -> Result<Vec<GlobSet>, ConfigurationError> {
+		self.configuration
+			.overrides
+			.iter()
+			.enumerate()
+			.map(|(index, configuration)| {
+				let include = [
+					JsonKey::Field("overrides"),
+					JsonKey::Index(index),
+					JsonKey::Field("include"),
+				];
+				let mut builder = GlobSetBuilder::new();
+				for pattern in &configuration.include {
+					let glob = GlobBuilder::new(pattern)
+						.literal_separator(true)
+						.build()
+						.map_err(|error| {
+							self.error_at_string(
+								&include,
+								pattern,
+								"invalid glob pattern",
+								error.kind().to_string(),
+							)
+						})?;
+					builder.add(glob);
+				}
+				builder.build().map_err(|error| {
+					let pattern = configuration.include.first().map_or("", String::as_str);
+					self.error_at_string(
+						&include,
+						pattern,
+						"invalid glob pattern",
+						error.to_string(),
+					)
+				})
+			})
+			.collect()
+	}
+
+	/// Creates an error on the string with the given content in the array or the object at the
+	/// path, or at the start of the file if it isn't found
+	fn error_at_string(
+		&self,
+		path: &[JsonKey],
+		content: &str,
+		title: &str,
+		message: String,
+	) -> ConfigurationError {
+		let source = blank_comments(self.file.source(0).unwrap_or_default());
+		// the range of the content, without the quotes
+		let range = find_string(&source, path, content)
+			.map_or(0..0, |range| range.start + 1..range.end - 1);
+		let diagnostic = Diagnostic::error(0, "Configuration", title).primary(range, message);
+
+		ConfigurationError {
+			file: self.file.clone(),
+			diagnostic: Box::new(diagnostic),
+		}
+	}
+}
+
+/// A step of the path to a value of the configuration file
+#[derive(Debug, Clone, Copy)]
+enum JsonKey<'a> {
+	/// The field of an object
+	Field(&'a str),
+	/// The item of an array
+	Index(usize),
+}
+
+/// Returns the range, quotes included, of the string with the given content in the array or the
+/// object at the path of the JSON source, which must not contain comments.
+///
+/// Only the values of the array or the object are looked at, so that the same string in another
+/// field, or in a key, isn't found instead.
+fn find_string(source: &str, path: &[JsonKey], content: &str) -> Option<Range<usize>> {
+	let mut value = JsonCursor::new(source).value()?;
+	for key in path {
+		let members = JsonCursor::new(source).members(value.start)?;
+		value = match key {
+			JsonKey::Field(field) => {
+				members
+					.into_iter()
+					.find(|(name, _)| name.as_deref() == Some(*field))?
+					.1
+			}
+			JsonKey::Index(index) => members.into_iter().nth(*index)?.1,
+		};
+	}
+
+	JsonCursor::new(source)
+		.members(value.start)?
+		.into_iter()
+		.map(|(_, value)| value)
+		.find(|value| {
+			serde_json::from_str::<String>(&source[value.clone()])
+				.ok()
+				.as_deref() == Some(content)
+		})
+}
+
+/// Reads the values of a valid JSON source, keeping their positions
+struct JsonCursor<'a> {
+	source: &'a str,
+	offset: usize,
+}
+
+impl<'a> JsonCursor<'a> {
+	fn new(source: &'a str) -> Self {
+		Self { source, offset: 0 }
+	}
+
+	fn peek(&self) -> Option<u8> {
+		self.source.as_bytes().get(self.offset).copied()
+	}
+
+	fn skip_whitespace(&mut self) {
+		while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+			self.offset += 1;
+		}
+	}
+
+	/// Skips the value at the cursor and returns its range
+	fn value(&mut self) -> Option<Range<usize>> {
+		self.skip_whitespace();
+		let start = self.offset;
+		match self.peek()? {
+			b'"' => {
+				self.offset += 1;
+				loop {
+					match self.peek()? {
+						b'\\' => self.offset += 2,
+						b'"' => break,
+						_ => self.offset += 1,
+					}
+				}
+				self.offset += 1;
+			}
+			b'{' | b'[' => {
+				self.members(start)?;
+			}
+			_ => {
+				while !matches!(
+					self.peek(),
+					None | Some(b',' | b'}' | b']' | b' ' | b'\t' | b'\n' | b'\r')
+				) {
+					self.offset += 1;
+				}
+			}
+		}
+		Some(start..self.offset)
+	}
+
+	/// Returns the members of the object or the array that starts at the offset, with the name
+	/// of each member of an object, and moves the cursor after its end
+	fn members(&mut self, offset: usize) -> Option<Vec<(Option<String>, Range<usize>)>> {
+		self.offset = offset;
+		let end = match self.peek()? {
+			b'{' => b'}',
+			b'[' => b']',
+			_ => return None,
+		};
+		self.offset += 1;
+
+		let mut members = Vec::new();
+		loop {
+			self.skip_whitespace();
+			match self.peek()? {
+				byte if byte == end => break,
+				b',' => self.offset += 1,
+				_ if end == b'}' => {
+					let name = self.value()?;
+					let name = serde_json::from_str(&self.source[name]).ok()?;
+					self.skip_whitespace();
+					if self.peek()? != b':' {
+						return None;
+					}
+					self.offset += 1;
+					members.push((Some(name), self.value()?));
+				}
+				_ => members.push((None, self.value()?)),
+			}
+		}
+		self.offset += 1;
+		Some(members)
+	}
+}
+
+/// An error in the configuration file, which points to the offending part of the JSON
+pub(crate) struct ConfigurationError {
+	file: SimpleFile,
+	diagnostic: Box<Diagnostic>,
+}
+
+impl ConfigurationError {
+	/// Prints the diagnostic to the standard error
+	pub fn emit(&self, color: bool) {
+		Emitter::new(&self.file)
+			.emit_stderr(&self.diagnostic, color)
+			.expect("failed to emit the diagnostic");
+	}
+}
+
+/// Looks for a configuration file in the directory and in its ancestors, and parses
+/// the first one that is found
+pub(crate) fn load_configuration(
+	directory: &Path,
+) -> Result<Option<LoadedConfiguration>, ConfigurationError> {
+	let path = match directory
+		.ancestors()
+		.map(|directory| directory.join(CONFIGURATION_FILE))
+		.find(|path| path.is_file())
+	{
+		Some(path) => path,
+		None => return Ok(None),
+	};
+
+	let name = path.display().to_string();
+	let source = match fs::read_to_string(&path) {
+		Ok(source) => source,
+		Err(error) => {
+			let diagnostic = Diagnostic::error(
+				0,
+				"Configuration",
+				format!("failed to read the configuration file: {}", error),
+			);
+			return Err(ConfigurationError {
+				file: SimpleFile::new(name, String::new()),
+				diagnostic: Box::new(diagnostic),
+			});
+		}
+	};
+
+	match parse_configuration(&source) {
+		Ok(configuration) => {
+			let mut loaded = LoadedConfiguration {
+				root: path.parent().map(Path::to_path_buf).unwrap_or_default(),
+				path,
+				configuration,
+				overrides: Vec::new(),
+				file: SimpleFile::new(name, source),
+			};
+			loaded.overrides = loaded.compile_overrides()?;
+			Ok(Some(loaded))
+		}
+		Err(diagnostic) => Err(ConfigurationError {
+			file: SimpleFile::new(name, source),
+			diagnostic,
+		}),
+	}
+}
+
+/// Deserializes the configuration, converting the errors to a [Diagnostic] on the offending character.
+///
+/// The configuration can contain `//` and `/* */` comments.
+pub(crate) fn parse_configuration(source: &str) -> Result<Configuration, Box<Diagnostic>> {
+	serde_json::from_str(&blank_comments(source)).map_err(|error| {
+		let offset = offset_of(source, error.line(), error.column());
+		let end = source[offset..]
+			.chars()
+			.next()
+			.map_or(offset, |c| offset + c.len_utf8());
+		// errors about a string value are reported on its closing quote, highlight the whole string
+		let start = match &source[offset..end] {
+			"\"" => source[..offset].rfind('"').unwrap_or(offset),
+			_ => offset,
+		};
+
+		// the message of serde_json ends with the position, which the diagnostic already shows
+		let message = error.to_string();
+		let message = match message.rfind(" at line ") {
+			Some(index) => &message[..index],
+			None => message.as_str(),
+		};
+
+		Box::new(
+			Diagnostic::error(0, "Configuration", "invalid configuration file")
+				.primary(start..end, message),
+		)
+	})
+}
+
+/// Replaces the comments with spaces, keeping the line breaks, so that the positions of the errors
+/// in the returned JSON are the same as in the source
+fn blank_comments(source: &str) -> String {
+	let mut result = String::with_capacity(source.len());
+	let mut chars = source.chars().peekable();
+	let mut in_string = false;
+
+	while let Some(c) = chars.next() {
+		if in_string {
+			result.push(c);
+			match c {
+				'\\' => result.extend(chars.next()),
+				'"' => in_string = false,
+				_ => {}
+			}
+			continue;
+		}
+
+		match (c, chars.peek()) {
+			('"', _) => {
+				in_string = true;
+				result.push(c);
+			}
+			('/', Some('/')) => {
+				result.push(' ');
+				while let Some(c) = chars.next_if(|c| *c != '\n') {
+					result.push_str(&" ".repeat(c.len_utf8()));
+				}
+			}
+			('/', Some('*')) => {
+				result.push(' ');
+				let mut previous = ' ';
+				for c in chars.by_ref() {
+					if c == '\n' {
+						result.push(c);
+					} else {
+						result.push_str(&" ".repeat(c.len_utf8()));
+					}
+					if previous == '*' && c == '/' {
+						break;
+					}
+					previous = c;
+				}
+			}
+			_ => result.push(c),
+		}
+	}
+
+	result
+}
+
+/// Converts a one-based line and column, as reported by serde_json, to a byte offset
+fn offset_of(source: &str, line: usize, column: usize) -> usize {
+	let line_start = source
+		.split_inclusive('\n')
+		.take(line.saturating_sub(1))
+		.map(str::len)
+		.sum::<usize>();
+
+	let offset = line_start + column.saturating_sub(1);
+	let mut offset = offset.min(source.len());
+	while !source.is_char_boundary(offset) {
+		offset -= 1;
+	}
+	offset
+}
+
+#[cfg(test)]
+mod test {
+	use super::{
+		blank_comments, find_string, offset_of, parse_configuration, IndentStyleConfiguration,
+		JsonKey,
+	};
+	use rome_core::file_handlers::Language;
+	use rome_formatter::IndentStyle;
+
+	#[test]
+	fn merges_the_language_options() {
+		let configuration = parse_configuration(
+			r#"{
+				"formatter": { "indentStyle": "space", "indentSize": 4, "lineWidth": 100 },
+				"json": { "formatter": { "indentStyle": "tab" } }
+			}"#,
+		)
+		.unwrap();
+
+		let js = configuration
+			.formatter_for(&Language::Js)
+			.to_format_options();
+		assert_eq!(js.indent_style, IndentStyle::Space(4));
+		assert_eq!(js.line_width, 100);
+
+		let json = configuration.formatter_for(&Language::Json);
+		assert_eq!(json.indent_style, Some(IndentStyleConfiguration::Tab));
+		assert_eq!(json.to_format_options().indent_style, IndentStyle::Tab);
+	}
+
+	#[test]
+	fn points_to_the_invalid_value() {
+		let source = "{\n\t\"formatter\": { \"indentStyle\": \"spaces\" }\n}";
+		let diagnostic = parse_configuration(source).unwrap_err();
+		let primary = diagnostic.primary.unwrap();
+
+		assert_eq!(
+			primary.msg,
+			"unknown variant `spaces`, expected `tab` or `space`"
+		);
+		assert_eq!(&source[primary.span.range], "\"spaces\"");
+	}
+
+	#[test]
+	fn finds_the_string_in_its_field() {
+		let source = r#"{
+			"files": { "ignore": ["src/[a"], "include": ["lib", "src/[a"] },
+			"overrides": [{ "include": ["src/[a"] }, { "include": ["src/[a"] }]
+		}"#;
+		let occurrences: Vec<_> = source
+			.match_indices("\"src/[a\"")
+			.map(|(offset, text)| offset..offset + text.len())
+			.collect();
+
+		let include = [JsonKey::Field("files"), JsonKey::Field("include")];
+		assert_eq!(
+			find_string(source, &include, "src/[a"),
+			Some(occurrences[1].clone())
+		);
+		let overrides = [
+			JsonKey::Field("overrides"),
+			JsonKey::Index(1),
+			JsonKey::Field("include"),
+		];
+		assert_eq!(
+			find_string(source, &overrides, "src/[a"),
+			Some(occurrences[3].clone())
+		);
+		assert_eq!(find_string(source, &include, "src"), None);
+	}
+
+	#[test]
+	fn ignores_comments() {
+		let source = "{\n\t// a comment\n\t\"a\": \"// not a comment\" /* a\n * b */\n}";
+		assert_eq!(
+			blank_comments(source),
+			"{\n\t            \n\t\"a\": \"// not a comment\"     \n       \n}"
+		);
+
+		let configuration =
+			parse_configuration("{\n\t// the options\n\t\"formatter\": { \"lineWidth\": 100 }\n}")
+				.unwrap();
+		assert_eq!(configuration.formatter.line_width, Some(100));
+	}
+
+	#[test]
+	fn computes_offsets_from_lines_and_columns() {
+		let source = "{\n\t\"a\": 1\n}";
+		assert_eq!(offset_of(source, 1, 1), 0);
+		assert_eq!(offset_of(source, 2, 2), 3);
+		assert_eq!(offset_of(source, 3, 2), source.len());
+	}
+}
@@ -0,0 +1,88 @@
+//! Rendering of the changes the formatter would apply to a file, as a unified diff
+use colored::Colorize;
+use similar::{ChangeTag, TextDiff};
+use std::fmt::Write;
+
+/// How many unchanged lines are printed before and after each change
+const CONTEXT_LINES: usize = 3;
+
+/// Returns the unified diff between the original content of the file and its formatted version.
+///
+/// The removed lines are colored in red and the added lines in green, unless colors
+/// have been turned off with [colored::control::set_override].
+pub(crate) fn unified_diff(path: &str, original: &str, formatted: &str) -> String {
+	let diff = TextDiff::from_lines(original, formatted);
+	let mut output = String::new();
+
+	writeln!(output, "{}", format!("--- a/{}", path).bold()).unwrap();
+	writeln!(output, "{}", format!("+++ b/{}", path).bold()).unwrap();
+
+	for hunk in diff
+		.unified_diff()
+		.context_radius(CONTEXT_LINES)
+		.iter_hunks()
+	{
+		writeln!(output, "{}", hunk.header().to_string().cyan()).unwrap();
+
+		for change in hunk.iter_changes() {
+			let line = format!("{}{}", change.tag(), change.value().trim_end_matches('\n'));
+			let line = match change.tag() {
+				ChangeTag::Delete => line.red(),
+				ChangeTag::Insert => line.green(),
+				ChangeTag::Equal => line.normal(),
+			};
+			writeln!(output, "{}", line).unwrap();
+
+			if change.missing_newline() {
+				writeln!(output, "\\ No newline at end of file").unwrap();
+			}
+		}
+	}
+
+	output
+}
+
+#[cfg(test)]
+mod test {
+	use super::unified_diff;
+
+	#[test]
+	fn prints_changed_lines_with_context() {
+		colored::control::set_override(false);
+
+		let original = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+		let formatted = "a\nb\nc\nd\ne\nF\ng\nh\ni\nj\n";
+
+		assert_eq!(
+			unified_diff("file.js", original, formatted),
+			"--- a/file.js
++++ b/file.js
+@@ -3,7 +3,7 @@
+ c
+ d
+ e
+-f
++F
+ g
+ h
+ i
+"
+		);
+	}
+
+	#[test]
+	fn reports_missing_newline_at_end_of_file() {
+		colored::control::set_override(false);
+
+		assert_eq!(
+			unified_diff("file.js", "let a =  1", "let a = 1;\n"),
+			"--- a/file.js
++++ b/file.js
+@@ -1 +1 @@
+-let a =  1
+\\ No newline at end of file
++let a = 1;
+"
+		);
+	}
+}
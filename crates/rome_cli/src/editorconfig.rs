@@ -0,0 +1,254 @@
+//! Support of the `.editorconfig` files, see <https://editorconfig.org>.
+//!
+//! The options of a file are read from the `.editorconfig` files of its directory and of its
+//! ancestors, up to the one that has `root = true`. The closest files, and the last sections
+//! of a file, take precedence. The options of `rome.json` and of the arguments override them.
+//!
+//! The supported properties are `indent_style`, `indent_size`, `end_of_line`
+//! and `max_line_length`.
+use crate::configuration::{
+	FormatterConfiguration, IndentStyleConfiguration, LineEndingConfiguration,
+};
+use globset::{GlobBuilder, GlobMatcher};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// The name of the EditorConfig files
+pub(crate) const EDITORCONFIG_FILE: &str = ".editorconfig";
+
+/// The `.editorconfig` files read so far, by directory, shared by the threads that format the files
+#[derive(Default)]
+pub(crate) struct EditorConfigs {
+	files: Mutex<HashMap<PathBuf, Option<Arc<EditorConfig>>>>,
+}
+
+impl EditorConfigs {
+	/// Returns the formatter options of the file, from the `.editorconfig` files of its ancestors
+	pub fn formatter_for(&self, path: &Path) -> FormatterConfiguration {
+		let path = match env::current_dir() {
+			Ok(current_dir) => current_dir.join(path),
+			Err(_) => path.to_path_buf(),
+		};
+
+		let mut configs = Vec::new();
+		for directory in path.ancestors().skip(1) {
+			if let Some(config) = self.load(directory) {
+				let root = config.root;
+				configs.push((directory, config));
+				if root {
+					break;
+				}
+			}
+		}
+
+		let mut formatter = FormatterConfiguration::default();
+		for (directory, config) in configs.iter().rev() {
+			if let Ok(relative_path) = path.strip_prefix(directory) {
+				config.apply(relative_path, &mut formatter);
+			}
+		}
+		formatter
+	}
+
+	/// Returns the `.editorconfig` file of the directory, if it has one
+	fn load(&self, directory: &Path) -> Option<Arc<EditorConfig>> {
+		if let Some(config) = self.files.lock().unwrap().get(directory) {
+			return config.clone();
+		}
+
+		let config = fs::read_to_string(directory.join(EDITORCONFIG_FILE))
+			.ok()
+			.map(|source| Arc::new(EditorConfig::parse(&source)));
+		self.files
+			.lock()
+			.unwrap()
+			.insert(directory.to_path_buf(), config.clone());
+		config
+	}
+}
+
+/// The content of an `.editorconfig` file
+struct EditorConfig {
+	/// Whether the files of the parent directories are ignored
+	root: bool,
+	sections: Vec<Section>,
+}
+
+/// The properties of the files that match a glob
+struct Section {
+	/// [None] if the glob is invalid, then the section never matches
+	glob: Option<GlobMatcher>,
+	properties: FormatterConfiguration,
+}
+
+impl EditorConfig {
+	/// Parses the file, ignoring the lines that are invalid and the unsupported properties
+	fn parse(source: &str) -> Self {
+		let mut root = false;
+		let mut sections: Vec<Section> = Vec::new();
+
+		for line in source.lines() {
+			let line = line.trim();
+			if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+				continue;
+			}
+
+			if let Some(glob) = line
+				.strip_prefix('[')
+				.and_then(|line| line.strip_suffix(']'))
+			{
+				sections.push(Section {
+					glob: glob_matcher(glob),
+					properties: FormatterConfiguration::default(),
+				});
+				continue;
+			}
+
+			let (key, value) = match line.split_once('=') {
+				Some((key, value)) => (key.trim().to_lowercase(), value.trim().to_lowercase()),
+				None => continue,
+			};
+			match sections.last_mut() {
+				Some(section) => section.set(&key, &value),
+				// the properties before the first section apply to the file itself
+				None => {
+					if key == "root" {
+						root = value == "true";
+					}
+				}
+			}
+		}
+
+		Self { root, sections }
+	}
+
+	/// Merges the properties of the sections that match the path, relative to the directory
+	/// of the file, into the formatter options
+	fn apply(&self, relative_path: &Path, formatter: &mut FormatterConfiguration) {
+		for section in &self.sections {
+			let matches = matches!(&section.glob, Some(glob) if glob.is_match(relative_path));
+			if matches {
+				formatter.merge(&section.properties);
+			}
+		}
+	}
+}
+
+impl Section {
+	fn set(&mut self, key: &str, value: &str) {
+		let properties = &mut self.properties;
+		match key {
+			"indent_style" => match value {
+				"tab" => properties.indent_style = Some(IndentStyleConfiguration::Tab),
+				"space" => properties.indent_style = Some(IndentStyleConfiguration::Space),
+				_ => {}
+			},
+			"indent_size" => {
+				// the value can also be `tab`, to use the width of the tabs
+				if let Ok(size) = value.parse() {
+					properties.indent_size = Some(size);
+				}
+			}
+			"end_of_line" => match value {
+				"lf" => properties.line_ending = Some(LineEndingConfiguration::Lf),
+				"crlf" => properties.line_ending = Some(LineEndingConfiguration::Crlf),
+				"cr" => properties.line_ending = Some(LineEndingConfiguration::Cr),
+				_ => {}
+			},
+			"max_line_length" => {
+				// the value can also be `off`
+				if let Ok(width) = value.parse() {
+					properties.line_width = Some(width);
+				}
+			}
+			_ => {}
+		}
+	}
+}
+
+/// Creates the matcher of a section. A glob without `/` matches the files of any directory,
+/// the other ones are relative to the directory of the `.editorconfig` file.
+fn glob_matcher(glob: &str) -> Option<GlobMatcher> {
+	let glob = if glob.contains('/') {
+		glob.trim_start_matches('/').to_string()
+	} else {
+		format!("**/{}", glob)
+	};
+	// a `**` within a component, like `lib/**.js`, matches any number of directories, while the
+	// matcher only supports it as a whole component
+	let glob = glob
+		.split('/')
+		.map(|component| match component.find("**") {
+			Some(index) if component != "**" => {
+				let (prefix, suffix) = (&component[..index], &component[index + 2..]);
+				match (prefix.is_empty(), suffix.is_empty()) {
+					(true, _) => format!("**/*{}", suffix),
+					(false, true) => format!("{}*/**", prefix),
+					(false, false) => format!("{}*/**/*{}", prefix, suffix),
+				}
+			}
+			_ => component.to_string(),
+		})
+		.collect::<Vec<_>>()
+		.join("/");
+
+	GlobBuilder::new(&glob)
+		.literal_separator(true)
+		.build()
+		.ok()
+		.map(|glob| glob.compile_matcher())
+}
+
+#[cfg(test)]
+mod test {
+	use super::EditorConfig;
+	use crate::configuration::{
+		FormatterConfiguration, IndentStyleConfiguration, LineEndingConfiguration,
+	};
+	use std::path::Path;
+
+	#[test]
+	fn applies_the_matching_sections_in_order() {
+		let config = EditorConfig::parse(
+			r#"
+# top-most EditorConfig file
+root = true
+
+[*]
+indent_style = space
+indent_size = 4
+end_of_line = CRLF
+
+[*.{json,yml}]
+indent_style = tab
+
+[lib/**.js]
+max_line_length = 100
+
+[[invalid]
+indent_size = 8
+"#,
+		);
+		assert!(config.root);
+
+		let options = |path| {
+			let mut formatter = FormatterConfiguration::default();
+			config.apply(Path::new(path), &mut formatter);
+			formatter
+		};
+
+		let json = options("src/data.json");
+		assert_eq!(json.indent_style, Some(IndentStyleConfiguration::Tab));
+		assert_eq!(json.indent_size, Some(4));
+		assert_eq!(json.line_ending, Some(LineEndingConfiguration::Crlf));
+		assert_eq!(json.line_width, None);
+
+		let lib = options("lib/nested/a.js");
+		assert_eq!(lib.indent_style, Some(IndentStyleConfiguration::Space));
+		assert_eq!(lib.line_width, Some(100));
+		assert_eq!(options("src/lib/a.js").line_width, None);
+	}
+}
@@ -1,8 +1,14 @@
 use clap::{crate_version, App, AppSettings, Arg};
 use rome_core::create_app;
-use rome_formatter::{format_file_and_save, FormatOptions, IndentStyle};
-use rome_path::RomePath;
-use std::{path::PathBuf, str::FromStr};
+use rome_formatter::{IndentStyle, LineEndingStyle};
+use std::str::FromStr;
+
+mod commands;
+mod configuration;
+mod diff;
+mod editorconfig;
+mod panic;
+mod watch;
 
 /// Main function to run Rome CLI
 pub fn run_cli() {
@@ -10,15 +16,20 @@ pub fn run_cli() {
 		.about("The official Rome CLI")
 		.version(crate_version!())
 		.setting(AppSettings::SubcommandRequiredElseHelp)
+		.arg(
+			Arg::new("no_color")
+				.long("no-color")
+				.about("Disable the colors in the output")
+				.global(true),
+		)
 		.subcommand(
 			App::new("format")
-				.about("Format a file")
+				.about("Format files, directories and glob patterns")
 				.arg(
 					Arg::new("indent_style")
 						.long("indent-style")
 						.about("The style of indentation")
 						.value_name("tab|space")
-						.default_value("tab")
 						.validator(|value| IndentStyle::from_str(value).map(|_| ())),
 				)
 				.arg(
@@ -26,7 +37,6 @@ pub fn run_cli() {
 						.long("indent-size")
 						.about("The size of the indent.")
 						.value_name("NUMBER")
-						.default_value("2")
 						.validator(|value| {
 							value
 								.parse::<u8>()
@@ -34,50 +44,142 @@ pub fn run_cli() {
 						}),
 				)
 				.arg(
-					Arg::new("input")
-						.about("File to format")
-						.required(true)
+					Arg::new("line_width")
+						.long("line-width")
+						.about("The max width of a line")
+						.value_name("NUMBER")
 						.validator(|value| {
-							let path = PathBuf::from(&value);
-							if !path.exists() {
-								return Err(format!("The file \"{}\" doesn't exist.", value));
-							}
-							Ok(())
+							value
+								.parse::<u16>()
+								.map_err(|_| "Invalid line-width value. Try using a number")
 						}),
+				)
+				.arg(
+					Arg::new("line_ending")
+						.long("line-ending")
+						.about("The line ending, \"auto\" keeps the one of each file")
+						.value_name("lf|crlf|cr|auto")
+						.validator(|value| LineEndingStyle::from_str(value).map(|_| ())),
+				)
+				.arg(
+					Arg::new("check")
+						.long("check")
+						.about("Fail if any file isn't formatted, without writing the files"),
+				)
+				.arg(
+					Arg::new("diff")
+						.long("diff")
+						.about("Print the changes needed to format the files, implies --check"),
+				)
+				.arg(
+					Arg::new("watch")
+						.long("watch")
+						.about("Keep running and format the files again when they change")
+						.conflicts_with_all(&["check", "diff"]),
+				)
+				.arg(
+					Arg::new("format_with_errors")
+						.long("format-with-errors")
+						.about("Also format the files with syntax errors, keeping invalid code"),
+				)
+				.arg(
+					Arg::new("use_gitignore")
+						.long("use-gitignore")
+						.about("Skip the files listed in the .gitignore files"),
+				)
+				.arg(
+					Arg::new("threads")
+						.long("threads")
+						.about(
+							"The number of threads used to format the files, defaults to the CPUs",
+						)
+						.value_name("NUMBER")
+						.validator(|value| match value.parse::<usize>() {
+							Ok(threads) if threads > 0 => Ok(()),
+							_ => Err("Invalid threads value. Try using a positive number"),
+						}),
+				)
+				.arg(
+					Arg::new("stdin_file_path")
+						.long("stdin-file-path")
+						.about("Format the standard input, using PATH to pick the language")
+						.value_name("PATH")
+						.conflicts_with_all(&["input", "check", "diff", "watch"]),
+				)
+				.arg(
+					Arg::new("input")
+						.about(
+							"Files, directories or glob patterns (e.g. \"src/**/*.js\") to format",
+						)
+						.required_unless_present("stdin_file_path")
+						.multiple_values(true),
 				),
 		)
+		.subcommand(
+			App::new("init")
+				.about("Create a rome.json with the default options in the current directory")
+				.arg(
+					Arg::new("force")
+						.long("force")
+						.about("Overwrite the rome.json that already exists"),
+				),
+		)
+		.subcommand(
+			App::new("parse")
+				.about("Print the syntax tree or the tokens of a file, to debug the parser")
+				.arg(
+					Arg::new("tokens")
+						.long("tokens")
+						.about("Print the tokens instead of the syntax tree"),
+				)
+				.arg(
+					Arg::new("script")
+						.long("script")
+						.about("Parse the file as a script"),
+				)
+				.arg(
+					Arg::new("module")
+						.long("module")
+						.about("Parse the file as a module")
+						.conflicts_with("script"),
+				)
+				.arg(
+					Arg::new("typescript")
+						.long("typescript")
+						.about("Parse the file as TypeScript")
+						.conflicts_with_all(&["script", "module"]),
+				)
+				.arg(Arg::new("input").about("The file to parse").required(true)),
+		)
 		.try_get_matches();
 	let subcommand_matches = match &matches {
 		Ok(r) => r.subcommand(),
 		Err(err) => err.exit(),
 	};
 
-	let app = create_app();
+	if let Some((_, matches)) = subcommand_matches {
+		if matches.is_present("no_color") {
+			colored::control::set_override(false);
+		}
+	}
 
-	match subcommand_matches {
-		Some(("format", matches)) => {
-			let size = matches.value_of("indent_size");
-			let style = matches.value_of("indent_style");
-			let input = matches.value_of("input").unwrap();
-			let options: IndentStyle = style
-				.map(|s| match s {
-					"tab" => IndentStyle::Tab,
-					"space" => {
-						let size = size.unwrap_or("2");
-						IndentStyle::Space(size.parse::<u8>().unwrap_or(2))
-					}
-					_ => IndentStyle::default(),
-				})
-				.unwrap_or_default();
+	// the panics of the formatter are reported as diagnostics instead of being printed
+	panic::install_panic_hook();
+	let mut app = create_app();
 
-			let mut file = RomePath::new(input).deduce_handler(&app);
-			format_file_and_save(&mut file, FormatOptions::new(options));
-		}
+	let success = match subcommand_matches {
+		Some(("format", matches)) => commands::format::run(&mut app, matches),
+		Some(("init", matches)) => commands::init::run(matches),
+		Some(("parse", matches)) => commands::parse::run(&app, matches),
 		// Thanks to the settings AppSettings::SubcommandRequiredElseHelp we should not be there
 		_ => clap::Error::with_description(
 			"Sub command not found".to_string(),
 			clap::ErrorKind::InvalidSubcommand,
 		)
 		.exit(),
+	};
+
+	if !success {
+		std::process::exit(1);
 	}
 }
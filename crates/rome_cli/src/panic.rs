@@ -0,0 +1,77 @@
+//! Catching the panics of the formatter, so that a bug only affects the file being formatted
+//! instead of aborting the whole run.
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+thread_local! {
+	/// Whether the thread runs a function of [catch_panic], whose panics aren't printed
+	static CATCHING_PANICS: Cell<bool> = Cell::new(false);
+
+	/// The location of the last panic caught on the thread, recorded by the panic hook
+	static PANIC_LOCATION: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Installs, once for the whole process, a panic hook that records the location of the panics
+/// caught by [catch_panic] instead of printing them. The other panics are printed by the
+/// previous hook.
+pub(crate) fn install_panic_hook() {
+	static INSTALL: Once = Once::new();
+	INSTALL.call_once(|| {
+		let previous_hook = panic::take_hook();
+		panic::set_hook(Box::new(move |info| {
+			if CATCHING_PANICS.with(Cell::get) {
+				let location = info.location().map(|location| location.to_string());
+				PANIC_LOCATION.with(|last| *last.borrow_mut() = location);
+			} else {
+				previous_hook(info);
+			}
+		}));
+	});
+}
+
+/// Runs the function and returns the message of its panic, if it panics.
+///
+/// The panic is only silenced, and its location added to the message, once the hook of
+/// [install_panic_hook] is installed.
+pub(crate) fn catch_panic<R>(function: impl FnOnce() -> R) -> Result<R, String> {
+	// a nested call leaves the flag set for the rest of the outer call
+	let was_catching = CATCHING_PANICS.with(|catching| catching.replace(true));
+	let result = panic::catch_unwind(AssertUnwindSafe(function));
+	CATCHING_PANICS.with(|catching| catching.set(was_catching));
+
+	result.map_err(|payload| {
+		let message = panic_message(payload.as_ref());
+		match PANIC_LOCATION.with(|last| last.borrow_mut().take()) {
+			Some(location) => format!("{} at {}", message, location),
+			None => message,
+		}
+	})
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+	if let Some(message) = payload.downcast_ref::<&str>() {
+		message.to_string()
+	} else if let Some(message) = payload.downcast_ref::<String>() {
+		message.clone()
+	} else {
+		String::from("unknown error")
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::{catch_panic, install_panic_hook};
+
+	#[test]
+	fn returns_the_message_and_the_location() {
+		install_panic_hook();
+		let result = catch_panic(|| -> () { panic!("formatter bug") });
+		let message = result.unwrap_err();
+
+		assert!(message.starts_with("formatter bug at "));
+		assert!(message.contains("panic.rs"));
+		assert_eq!(catch_panic(|| 1), Ok(1));
+	}
+}
@@ -0,0 +1,93 @@
+//! Watching the files for changes, for `rome format --watch`.
+//!
+//! The notifications of the OS are debounced: the files that change within a short delay of each
+//! other, like the files saved at once by an editor or written by `git checkout`, are reported
+//! together once the burst is over.
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+/// The delay without any change after which a burst of changes is reported
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(200);
+
+/// Watches files for changes
+pub(crate) struct FileWatcher {
+	// the notifications stop when the watcher is dropped
+	_watcher: RecommendedWatcher,
+	receiver: Receiver<DebouncedEvent>,
+}
+
+impl FileWatcher {
+	/// Starts watching the paths, recursively for the directories. The changes made after this
+	/// function returns are reported by [FileWatcher::run]
+	pub fn new(paths: &[PathBuf]) -> notify::Result<Self> {
+		let (sender, receiver) = channel();
+		let mut watcher = notify::watcher(sender, DEBOUNCE_DELAY)?;
+		for path in paths {
+			watcher.watch(path, RecursiveMode::Recursive)?;
+		}
+
+		Ok(Self {
+			_watcher: watcher,
+			receiver,
+		})
+	}
+
+	/// Calls the function with the files that were created or modified after each burst
+	/// of changes, sorted by path. Only returns if the watcher stops
+	pub fn run(self, mut on_change: impl FnMut(Vec<PathBuf>)) {
+		while let Ok(event) = self.receiver.recv() {
+			let mut changed = BTreeSet::new();
+			changed.extend(changed_path(event));
+			loop {
+				match self.receiver.recv_timeout(DEBOUNCE_DELAY) {
+					Ok(event) => changed.extend(changed_path(event)),
+					Err(RecvTimeoutError::Timeout) => break,
+					Err(RecvTimeoutError::Disconnected) => return,
+				}
+			}
+
+			if !changed.is_empty() {
+				on_change(changed.into_iter().collect());
+			}
+		}
+	}
+}
+
+/// Returns the file whose content may have changed with the event
+fn changed_path(event: DebouncedEvent) -> Option<PathBuf> {
+	match event {
+		DebouncedEvent::Create(path) | DebouncedEvent::Write(path) => Some(path),
+		DebouncedEvent::Rename(_, to) => Some(to),
+		_ => None,
+	}
+}
+
+/// The content written to each file by the watcher itself, so that the notifications of its
+/// own writes don't format the files again
+#[derive(Default)]
+pub(crate) struct OwnWrites {
+	hashes: HashMap<PathBuf, u64>,
+}
+
+impl OwnWrites {
+	/// Records that the content has been written to the file
+	pub fn record(&mut self, path: &Path, content: &str) {
+		self.hashes.insert(path.to_path_buf(), hash(content));
+	}
+
+	/// Returns `true` if the content is the last one written to the file by the watcher
+	pub fn is_own_write(&self, path: &Path, content: &str) -> bool {
+		self.hashes.get(path) == Some(&hash(content))
+	}
+}
+
+fn hash(content: &str) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	content.hash(&mut hasher);
+	hasher.finish()
+}
@@ -1,4 +1,15 @@
-use std::{env, path::PathBuf, process::Command};
+use std::{
+	env, fs,
+	io::{BufRead, BufReader, Write},
+	path::PathBuf,
+	process::{Child, Command, Output, Stdio},
+	sync::mpsc,
+	thread,
+	time::{Duration, Instant},
+};
+
+/// How long the tests wait for the output of a command that keeps running
+const OUTPUT_TIMEOUT: Duration = Duration::from_secs(10);
 
 fn cargo_bin(name: &str) -> PathBuf {
 	env::current_exe()
@@ -13,6 +24,125 @@ fn cargo_bin(name: &str) -> PathBuf {
 		.expect("cannot get current exe")
 }
 
+/// A directory inside the system temporary directory, removed when dropped
+struct TestDirectory {
+	path: PathBuf,
+}
+
+impl TestDirectory {
+	fn new(name: &str) -> Self {
+		let path = env::temp_dir().join(format!("rome_cli_{}_{}", name, std::process::id()));
+		if path.exists() {
+			fs::remove_dir_all(&path).unwrap();
+		}
+		fs::create_dir_all(&path).unwrap();
+		Self { path }
+	}
+
+	fn write(&self, file: &str, content: &str) {
+		let file = self.path.join(file);
+		fs::create_dir_all(file.parent().unwrap()).unwrap();
+		fs::write(file, content).unwrap();
+	}
+
+	fn read(&self, file: &str) -> String {
+		fs::read_to_string(self.path.join(file)).unwrap()
+	}
+
+	fn run(&self, args: &[&str]) -> Output {
+		Command::new(cargo_bin("cli"))
+			.args(args)
+			.current_dir(&self.path)
+			.output()
+			.expect("fail to run cli")
+	}
+
+	fn run_with_stdin(&self, args: &[&str], stdin: &str) -> Output {
+		let mut child = Command::new(cargo_bin("cli"))
+			.args(args)
+			.current_dir(&self.path)
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::piped())
+			.spawn()
+			.expect("fail to run cli");
+		child
+			.stdin
+			.take()
+			.unwrap()
+			.write_all(stdin.as_bytes())
+			.unwrap();
+		child.wait_with_output().unwrap()
+	}
+}
+
+impl Drop for TestDirectory {
+	fn drop(&mut self) {
+		let _ = fs::remove_dir_all(&self.path);
+	}
+}
+
+/// A running command, killed when dropped so that a failing test doesn't leave it running
+struct RunningCommand {
+	child: Child,
+	lines: mpsc::Receiver<String>,
+}
+
+impl RunningCommand {
+	/// Spawns the command, whose standard output is read line by line on another thread
+	fn spawn(command: &mut Command) -> Self {
+		let mut child = command
+			.stdout(Stdio::piped())
+			.spawn()
+			.expect("fail to run cli");
+		let stdout = child.stdout.take().unwrap();
+		let (sender, lines) = mpsc::channel();
+		thread::spawn(move || {
+			// stops when the command exits or when the test doesn't wait for its output anymore
+			for line in BufReader::new(stdout).lines() {
+				match line.map(|line| sender.send(line)) {
+					Ok(Ok(())) => {}
+					_ => break,
+				}
+			}
+		});
+		Self { child, lines }
+	}
+
+	/// Returns the lines printed until the expected one, included.
+	///
+	/// Panics if the line isn't printed within [OUTPUT_TIMEOUT].
+	fn wait_for_line(&self, expected: &str) -> Vec<String> {
+		let deadline = Instant::now() + OUTPUT_TIMEOUT;
+		let mut lines = Vec::new();
+		loop {
+			let timeout = deadline.saturating_duration_since(Instant::now());
+			match self.lines.recv_timeout(timeout) {
+				Ok(line) if line == expected => {
+					lines.push(line);
+					return lines;
+				}
+				Ok(line) => lines.push(line),
+				Err(error) => panic!(
+					"the line {:?} wasn't printed ({}), the output was {:?}",
+					expected, error, lines
+				),
+			}
+		}
+	}
+}
+
+impl Drop for RunningCommand {
+	fn drop(&mut self) {
+		let _ = self.child.kill();
+		let _ = self.child.wait();
+	}
+}
+
+fn stdout(output: &Output) -> String {
+	String::from_utf8(output.stdout.clone()).expect("cannot read stdout, not utf8 compliant")
+}
+
 #[test]
 #[ignore = "The CLI for now, doesn't print anything"]
 fn test_format_cli() {
@@ -34,3 +164,541 @@ fn test_format_cli() {
 "#
 	);
 }
+
+#[test]
+fn format_directories_and_globs() {
+	let dir = TestDirectory::new("format_directories_and_globs");
+	dir.write("src/a.js", "let a =   [1,2]");
+	dir.write("src/nested/b.json", "{ \"a\":   1 }");
+	dir.write("src/readme.md", "#   Not formatted");
+	dir.write("tests/c.js", "let c =   3");
+	dir.write("tests/c.json", "{ \"c\":   3 }");
+
+	let output = dir.run(&["format", "src", "tests/**/*.js"]);
+	assert!(output.status.success(), "cli format command failed");
+
+	assert_eq!(dir.read("src/a.js"), "let a = [1, 2];\n");
+	assert_eq!(dir.read("src/nested/b.json"), "{\"a\": 1}\n");
+	assert_eq!(dir.read("src/readme.md"), "#   Not formatted");
+	assert_eq!(dir.read("tests/c.js"), "let c = 3;\n");
+	assert_eq!(dir.read("tests/c.json"), "{ \"c\":   3 }");
+
+	assert_eq!(
+		stdout(&output),
+		"Formatted src/a.js
+Formatted src/nested/b.json
+Formatted tests/c.js
+Formatted 3 file(s), 0 failed, 1 skipped
+"
+	);
+}
+
+#[test]
+fn format_globs_relative_to_the_current_directory() {
+	let dir = TestDirectory::new("format_globs_relative_to_the_current_directory");
+	dir.write("src/a.js", "let a =   1");
+	dir.write("src/nested/b.js", "let b =   2");
+
+	let output = dir.run(&["format", "./src/**/*.js"]);
+	assert!(output.status.success(), "cli format command failed");
+
+	assert_eq!(dir.read("src/a.js"), "let a = 1;\n");
+	assert_eq!(dir.read("src/nested/b.js"), "let b = 2;\n");
+	assert_eq!(
+		stdout(&output),
+		"Formatted src/a.js
+Formatted src/nested/b.js
+Formatted 2 file(s), 0 failed, 0 skipped
+"
+	);
+}
+
+#[test]
+fn check_reports_unformatted_files() {
+	let dir = TestDirectory::new("check_reports_unformatted_files");
+	dir.write("formatted.js", "let a = [1, 2];\n");
+	dir.write("unformatted.js", "let b =   [1,2]");
+
+	let output = dir.run(&["format", "--check", "."]);
+	assert_eq!(output.status.code(), Some(1));
+
+	assert_eq!(dir.read("unformatted.js"), "let b =   [1,2]");
+	assert_eq!(
+		stdout(&output),
+		"Not formatted unformatted.js
+Checked 2 file(s), 1 not formatted, 0 failed, 0 skipped
+"
+	);
+}
+
+#[test]
+fn check_succeeds_when_files_are_formatted() {
+	let dir = TestDirectory::new("check_succeeds_when_files_are_formatted");
+	dir.write("formatted.js", "let a = [1, 2];\n");
+	dir.write("formatted.json", "{\"a\": 1}\n");
+
+	let output = dir.run(&["format", "--check", "."]);
+	assert!(output.status.success(), "cli format --check failed");
+	assert_eq!(
+		stdout(&output),
+		"Checked 2 file(s), 0 not formatted, 0 failed, 0 skipped\n"
+	);
+}
+
+#[test]
+fn diff_prints_the_changes_of_each_file() {
+	let dir = TestDirectory::new("diff_prints_the_changes_of_each_file");
+	dir.write("a.js", "let a =   1;\n");
+	dir.write("b.json", "{ \"b\":   2 }\n");
+	dir.write("c.js", "let c = 3;\n");
+
+	let output = dir.run(&["format", "--diff", "--no-color", "."]);
+	assert_eq!(output.status.code(), Some(1));
+
+	assert_eq!(dir.read("a.js"), "let a =   1;\n");
+	assert_eq!(
+		stdout(&output),
+		r#"--- a/a.js
++++ b/a.js
+@@ -1 +1 @@
+-let a =   1;
++let a = 1;
+--- a/b.json
++++ b/b.json
+@@ -1 +1 @@
+-{ "b":   2 }
++{"b": 2}
+Checked 3 file(s), 2 not formatted, 0 failed, 0 skipped
+"#
+	);
+}
+
+#[test]
+fn format_stdin_and_print_to_stdout() {
+	let dir = TestDirectory::new("format_stdin_and_print_to_stdout");
+
+	let output = dir.run_with_stdin(
+		&["format", "--stdin-file-path", "does/not/exist.js"],
+		"let a =   [1,2]",
+	);
+
+	assert!(
+		output.status.success(),
+		"cli format --stdin-file-path failed"
+	);
+	assert_eq!(stdout(&output), "let a = [1, 2];\n");
+	assert!(!dir.path.join("does").exists());
+}
+
+#[test]
+fn configuration_sets_the_formatter_options() {
+	let dir = TestDirectory::new("configuration_sets_the_formatter_options");
+	dir.write(
+		"rome.json",
+		r#"{
+	"formatter": { "indentStyle": "space", "indentSize": 4 },
+	"files": { "ignore": ["src/generated/**"] },
+	"json": { "formatter": { "indentStyle": "tab", "lineWidth": 10 } }
+}"#,
+	);
+	dir.write("src/a.js", "function f() { return 1 }");
+	dir.write("src/b.json", "{\"a\": [1, 2]}");
+	dir.write("src/generated/c.js", "let c =   3");
+
+	let output = dir.run(&["format", "src"]);
+	assert!(output.status.success(), "cli format command failed");
+
+	assert_eq!(dir.read("src/a.js"), "function f() {\n    return 1;\n}\n");
+	assert_eq!(
+		dir.read("src/b.json"),
+		"{\n\t\"a\": [\n\t\t1,\n\t\t2\n\t]\n}\n"
+	);
+	assert_eq!(dir.read("src/generated/c.js"), "let c =   3");
+}
+
+#[test]
+fn arguments_override_the_configuration() {
+	let dir = TestDirectory::new("arguments_override_the_configuration");
+	dir.write(
+		"rome.json",
+		r#"{ "formatter": { "indentStyle": "space" } }"#,
+	);
+	dir.write("a.js", "function f() { return 1 }");
+
+	let output = dir.run(&["format", "--indent-style", "tab", "a.js"]);
+	assert!(output.status.success(), "cli format command failed");
+
+	assert_eq!(dir.read("a.js"), "function f() {\n\treturn 1;\n}\n");
+}
+
+#[test]
+fn line_endings_and_byte_order_marks_are_kept() {
+	let dir = TestDirectory::new("line_endings_and_byte_order_marks_are_kept");
+	dir.write("rome.json", r#"{ "formatter": { "lineEnding": "auto" } }"#);
+	dir.write("a.js", "\u{feff}let a =   1;\r\nlet b = 2");
+	dir.write("b.js", "let a =   1;\nlet b = 2");
+
+	let output = dir.run(&["format", "a.js", "b.js"]);
+	assert!(output.status.success(), "cli format command failed");
+	assert_eq!(dir.read("a.js"), "\u{feff}let a = 1;\r\nlet b = 2;\r\n");
+	assert_eq!(dir.read("b.js"), "let a = 1;\nlet b = 2;\n");
+
+	let output = dir.run(&["format", "--line-ending", "crlf", "b.js"]);
+	assert!(output.status.success(), "cli format command failed");
+	assert_eq!(dir.read("b.js"), "let a = 1;\r\nlet b = 2;\r\n");
+}
+
+#[test]
+fn editorconfig_files_set_the_formatter_options() {
+	let dir = TestDirectory::new("editorconfig_files_set_the_formatter_options");
+	dir.write(
+		".editorconfig",
+		"root = true\n\n[*]\nindent_style = space\nindent_size = 2\n",
+	);
+	dir.write("lib/.editorconfig", "[*.js]\nindent_size = 4\n");
+	dir.write("a.js", "if (a) {   b }");
+	dir.write("lib/b.js", "if (a) {   b }");
+
+	let output = dir.run(&["format", "."]);
+	assert!(output.status.success(), "cli format command failed");
+	assert_eq!(dir.read("a.js"), "if (a) {\n  b;\n}\n");
+	assert_eq!(dir.read("lib/b.js"), "if (a) {\n    b;\n}\n");
+
+	// the configuration and the arguments override the `.editorconfig` files
+	dir.write("rome.json", r#"{ "formatter": { "indentSize": 3 } }"#);
+	let output = dir.run(&["format", "a.js"]);
+	assert!(output.status.success(), "cli format command failed");
+	assert_eq!(dir.read("a.js"), "if (a) {\n   b;\n}\n");
+
+	let output = dir.run(&["format", "--indent-style", "tab", "lib/b.js"]);
+	assert!(output.status.success(), "cli format command failed");
+	assert_eq!(dir.read("lib/b.js"), "if (a) {\n\tb;\n}\n");
+}
+
+#[test]
+fn overrides_apply_to_the_matching_files_in_order() {
+	let dir = TestDirectory::new("overrides_apply_to_the_matching_files_in_order");
+	dir.write(
+		"rome.json",
+		r#"{
+			"formatter": { "indentStyle": "space" },
+			"overrides": [
+				{ "include": ["tests/**"], "formatter": { "indentSize": 4 } },
+				{ "include": ["tests/fixtures/**"], "formatter": { "enabled": false } },
+				{ "include": ["src/generated/*.js"], "formatter": { "indentStyle": "tab" } }
+			]
+		}"#,
+	);
+	dir.write("src/a.js", "if (a) {   b }");
+	dir.write("src/generated/b.js", "if (a) {   b }");
+	dir.write("tests/c.js", "if (a) {   b }");
+	dir.write("tests/fixtures/d.js", "if (a) {   b }");
+
+	let output = dir.run(&["format", "."]);
+	assert!(output.status.success(), "cli format command failed");
+	assert_eq!(dir.read("src/a.js"), "if (a) {\n  b;\n}\n");
+	assert_eq!(dir.read("src/generated/b.js"), "if (a) {\n\tb;\n}\n");
+	assert_eq!(dir.read("tests/c.js"), "if (a) {\n    b;\n}\n");
+	assert_eq!(dir.read("tests/fixtures/d.js"), "if (a) {   b }");
+	assert!(stdout(&output).ends_with("Formatted 3 file(s), 0 failed, 2 skipped\n"));
+
+	let output = dir.run_with_stdin(
+		&["format", "--stdin-file-path", "tests/fixtures/e.js"],
+		"if (a) {   b }",
+	);
+	assert!(output.status.success(), "cli format command failed");
+	assert_eq!(stdout(&output), "if (a) {   b }");
+}
+
+#[test]
+fn invalid_configuration_is_reported() {
+	let dir = TestDirectory::new("invalid_configuration_is_reported");
+	dir.write(
+		"rome.json",
+		r#"{ "formatter": { "indentStyle": "spaces" } }"#,
+	);
+	dir.write("a.js", "let a =   1");
+
+	let output = dir.run(&["format", "--no-color", "a.js"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert_eq!(dir.read("a.js"), "let a =   1");
+
+	let stderr = String::from_utf8(output.stderr).unwrap();
+	assert!(stderr.contains("error[Configuration]: invalid configuration file"));
+	assert!(stderr.contains("rome.json:1:33"));
+	assert!(stderr.contains("unknown variant `spaces`, expected `tab` or `space`"));
+}
+
+#[test]
+fn configuration_registers_extensions_and_file_names() {
+	let dir = TestDirectory::new("configuration_registers_extensions_and_file_names");
+	dir.write(
+		"rome.json",
+		r#"{ "files": { "extensions": { "es6": "js" }, "fileNames": { ".babelrc": "json" } } }"#,
+	);
+	dir.write("a.es6", "let a =   1");
+	dir.write("config/.babelrc", "{ \"b\":   1 }");
+
+	let output = dir.run(&["format", "."]);
+	assert!(output.status.success(), "cli format command failed");
+	assert_eq!(dir.read("a.es6"), "let a = 1;\n");
+	assert_eq!(dir.read("config/.babelrc"), "{\"b\": 1}\n");
+
+	dir.write(
+		"rome.json",
+		r#"{ "files": { "extensions": { "es6": "es" } } }"#,
+	);
+	let output = dir.run(&["format", "--no-color", "."]);
+	assert_eq!(output.status.code(), Some(1));
+	let stderr = String::from_utf8(output.stderr).unwrap();
+	assert!(stderr.contains("error[Configuration]: unknown extension"));
+	assert!(stderr.contains("rome.json:1:38"));
+}
+
+#[test]
+fn ignore_files_are_honored() {
+	let dir = TestDirectory::new("ignore_files_are_honored");
+	dir.write(".romeignore", "node_modules/\n");
+	dir.write(".gitignore", "build/\n");
+	dir.write("a.js", "let a =   1");
+	dir.write("node_modules/b.js", "let b =   2");
+	dir.write("build/c.js", "let c =   3");
+
+	let output = dir.run(&["format", "."]);
+	assert!(output.status.success(), "cli format command failed");
+	assert_eq!(dir.read("node_modules/b.js"), "let b =   2");
+	assert_eq!(dir.read("build/c.js"), "let c = 3;\n");
+
+	dir.write("build/c.js", "let c =   3");
+	let output = dir.run(&["format", "--use-gitignore", "."]);
+	assert!(output.status.success(), "cli format command failed");
+	assert_eq!(dir.read("build/c.js"), "let c =   3");
+	assert_eq!(
+		stdout(&output),
+		"Formatted a.js\nFormatted 1 file(s), 0 failed, 2 skipped\n"
+	);
+}
+
+#[test]
+fn parallel_formatting_prints_the_files_in_order() {
+	let dir = TestDirectory::new("parallel_formatting_prints_the_files_in_order");
+	let mut expected = String::new();
+	for index in 0..20 {
+		dir.write(&format!("src/file_{:02}.js", index), "let a =   1");
+		expected.push_str(&format!("Formatted src/file_{:02}.js\n", index));
+	}
+	expected.push_str("Formatted 20 file(s), 0 failed, 0 skipped\n");
+
+	let output = dir.run(&["format", "--threads", "4", "src"]);
+	assert!(output.status.success(), "cli format command failed");
+	assert_eq!(stdout(&output), expected);
+	assert_eq!(dir.read("src/file_19.js"), "let a = 1;\n");
+
+	let output = dir.run(&["format", "--threads", "0", "src"]);
+	assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn init_creates_the_configuration_file() {
+	let dir = TestDirectory::new("init_creates_the_configuration_file");
+
+	let output = dir.run(&["init"]);
+	assert!(output.status.success(), "cli init command failed");
+	assert!(stdout(&output).starts_with("Created \"rome.json\"\n"));
+	let configuration = dir.read("rome.json");
+	assert!(configuration.contains("\"indentStyle\": \"tab\""));
+	assert!(configuration.contains("\"lineWidth\": 80"));
+
+	// the configuration is valid and isn't formatted itself
+	dir.write("a.js", "let a =   1");
+	let output = dir.run(&["format", "."]);
+	assert!(output.status.success(), "cli format command failed");
+	assert_eq!(dir.read("rome.json"), configuration);
+	assert_eq!(dir.read("a.js"), "let a = 1;\n");
+
+	dir.write("rome.json", "{}");
+	let output = dir.run(&["init"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert_eq!(dir.read("rome.json"), "{}");
+
+	let output = dir.run(&["init", "--force"]);
+	assert!(output.status.success(), "cli init --force command failed");
+	assert_eq!(dir.read("rome.json"), configuration);
+}
+
+#[test]
+fn each_flavor_is_parsed_in_its_own_mode() {
+	let dir = TestDirectory::new("each_flavor_is_parsed_in_its_own_mode");
+	dir.write("a.mjs", "import a from \"a\"\nlet b =   a");
+	dir.write("b.cjs", "with (a) {   b }");
+	dir.write("c.ts", "let c: number =   1");
+	dir.write("d.jsx", "let d =   <div />");
+
+	// the JSX files are reported, since the parser doesn't support JSX yet
+	let output = dir.run(&["format", "."]);
+	assert!(!output.status.success(), "cli format command succeeded");
+	let stderr = String::from_utf8(output.stderr.clone()).unwrap();
+	assert!(stderr.contains("d.jsx: the language of the file is not supported"));
+
+	assert_eq!(dir.read("a.mjs"), "import a from \"a\"\nlet b = a;\n");
+	assert_eq!(dir.read("b.cjs"), "with (a) {\n\tb;\n}\n");
+	assert_eq!(dir.read("c.ts"), "let c: number = 1;\n");
+	assert_eq!(dir.read("d.jsx"), "let d =   <div />");
+	assert_eq!(
+		stdout(&output),
+		"Formatted a.mjs
+Formatted b.cjs
+Formatted c.ts
+Formatted 3 file(s), 1 failed, 0 skipped
+"
+	);
+
+	let output = dir.run(&["parse", "c.ts"]);
+	assert!(output.status.success(), "cli parse command failed");
+	assert!(stdout(&output).contains("TS_NUMBER"));
+}
+
+#[test]
+fn files_without_extension_are_detected_from_their_content() {
+	let dir = TestDirectory::new("files_without_extension_are_detected_from_their_content");
+	dir.write("bin/deploy", "#!/usr/bin/env node\nlet a =   1");
+	dir.write("bin/build", "#!/bin/sh\nexit   0");
+	dir.write("data", "{ \"a\":   1 }");
+
+	let output = dir.run(&["format", "."]);
+	assert!(output.status.success(), "cli format command failed");
+
+	assert_eq!(dir.read("bin/deploy"), "#!/usr/bin/env node\nlet a = 1;\n");
+	assert_eq!(dir.read("bin/build"), "#!/bin/sh\nexit   0");
+	assert_eq!(dir.read("data"), "{\"a\": 1}\n");
+	assert_eq!(
+		stdout(&output),
+		"Formatted bin/deploy
+Formatted data
+Formatted 2 file(s), 0 failed, 1 skipped
+"
+	);
+}
+
+#[test]
+fn watch_formats_the_files_when_they_change() {
+	let dir = TestDirectory::new("watch_formats_the_files_when_they_change");
+	dir.write(".romeignore", "ignored/\n");
+	dir.write("a.js", "let a =   1");
+
+	let command = RunningCommand::spawn(
+		Command::new(cargo_bin("cli"))
+			.args(["format", "--watch", "."])
+			.current_dir(&dir.path),
+	);
+
+	assert_eq!(
+		command.wait_for_line("Watching for changes, press Ctrl+C to stop"),
+		[
+			"Formatted a.js",
+			"Formatted 1 file(s), 0 failed, 1 skipped",
+			"Watching for changes, press Ctrl+C to stop"
+		]
+	);
+	assert_eq!(dir.read("a.js"), "let a = 1;\n");
+
+	dir.write("ignored/b.js", "let b =   2");
+	dir.write("c.js", "let c =   3");
+	let mut lines = command.wait_for_line("Formatted c.js");
+	dir.write("a.js", "let a = 1;\n");
+	lines.extend(command.wait_for_line("Already formatted a.js"));
+
+	drop(command);
+	assert_eq!(dir.read("c.js"), "let c = 3;\n");
+	assert_eq!(dir.read("ignored/b.js"), "let b =   2");
+	assert!(
+		lines.iter().all(|line| !line.contains("ignored")),
+		"the ignored file was formatted: {:?}",
+		lines
+	);
+}
+
+#[test]
+fn parse_prints_the_tree_and_the_diagnostics() {
+	let dir = TestDirectory::new("parse_prints_the_tree_and_the_diagnostics");
+	dir.write("a.js", "a; // b");
+	dir.write("b.js", "export let =");
+
+	let output = dir.run(&["parse", "a.js"]);
+	assert!(output.status.success(), "cli parse command failed");
+	assert_eq!(
+		stdout(&output),
+		r#"0: JS_SCRIPT@0..7
+  0: (empty)
+  1: LIST@0..0
+  2: LIST@0..7
+    0: JS_EXPRESSION_STATEMENT@0..7
+      0: JS_IDENTIFIER_EXPRESSION@0..1
+        0: JS_REFERENCE_IDENTIFIER@0..1
+          0: IDENT@0..1 "a" [] []
+      1: SEMICOLON@1..7 ";" [] [Whitespace(" "), Comments("// b")]
+  3: EOF@7..7 "" [] []
+"#
+	);
+
+	let output = dir.run(&["parse", "--tokens", "a.js"]);
+	assert_eq!(
+		stdout(&output),
+		r#"IDENT@0..1 "a"
+SEMICOLON@1..2 ";"
+WHITESPACE@2..3 " "
+COMMENT@3..7 "// b"
+EOF@7..7 ""
+"#
+	);
+
+	let output = dir.run(&["parse", "--module", "--no-color", "b.js"]);
+	assert_eq!(output.status.code(), Some(1));
+	let stderr = String::from_utf8(output.stderr).unwrap();
+	assert!(stderr.contains("error[SyntaxError]"));
+	assert!(stderr.contains("b.js:1:"));
+}
+
+#[test]
+fn files_with_syntax_errors_are_only_formatted_on_demand() {
+	let dir = TestDirectory::new("files_with_syntax_errors_are_only_formatted_on_demand");
+	dir.write("invalid.js", "let a =   1;\nif test {   a }");
+
+	let output = dir.run(&["format", "--no-color", "invalid.js"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert_eq!(dir.read("invalid.js"), "let a =   1;\nif test {   a }");
+	let stderr = String::from_utf8(output.stderr).unwrap();
+	assert!(stderr.contains("error[SyntaxError]"));
+	assert!(stderr.contains("invalid.js:2:"));
+	assert!(stderr.contains("error[Format]: invalid.js: the file contains"));
+
+	let output = dir.run_with_stdin(
+		&["format", "--no-color", "--stdin-file-path", "stdin.js"],
+		"let a =   1;\nif test {   a }",
+	);
+	assert_eq!(output.status.code(), Some(1));
+	let stderr = String::from_utf8(output.stderr).unwrap();
+	assert!(stderr.contains("stdin.js:2:"));
+
+	let output = dir.run(&["format", "--format-with-errors", "invalid.js"]);
+	assert!(output.status.success(), "cli format command failed");
+	assert_eq!(dir.read("invalid.js"), "let a = 1;\nif test {   a }\n");
+}
+
+#[test]
+fn failures_are_reported_and_the_other_files_are_formatted() {
+	let dir = TestDirectory::new("failures_are_reported_and_the_other_files_are_formatted");
+	fs::write(dir.path.join("invalid.js"), b"let a = '\xff';").unwrap();
+	dir.write("valid.js", "let b =   1");
+
+	let output = dir.run(&["format", "--no-color", "."]);
+	assert_eq!(output.status.code(), Some(1));
+	assert_eq!(dir.read("valid.js"), "let b = 1;\n");
+	assert_eq!(
+		stdout(&output),
+		"Formatted valid.js\nFormatted 1 file(s), 1 failed, 0 skipped\n"
+	);
+
+	let stderr = String::from_utf8(output.stderr).unwrap();
+	assert!(stderr.contains("error[IO]: invalid.js: the content of the file isn't valid UTF-8"));
+}
@@ -0,0 +1,130 @@
+//! Detection of the language of the files without an extension, from their content.
+//!
+//! The detection returns the extension whose handler processes the file, so that it follows the
+//! handlers registered in the [App](crate::App). It looks, in order, at:
+//! - the shebang, e.g. `#!/usr/bin/env node`
+//! - a Vim or Emacs modeline in the first lines, e.g. `// vim: set ft=javascript:`
+//! - the first character, a `{` or a `[` being the start of a JSON document
+
+use crate::BYTE_ORDER_MARK;
+
+/// The number of lines at the start of the content that are searched for a modeline
+const MODELINE_LINES: usize = 5;
+
+/// Returns the extension of the files with the same language as the content
+pub(crate) fn detect_extension(content: &str) -> Option<&'static str> {
+	let content = content.strip_prefix(BYTE_ORDER_MARK).unwrap_or(content);
+
+	if let Some(shebang) = content.strip_prefix("#!") {
+		let line = shebang.lines().next().unwrap_or_default();
+		if let Some(extension) = extension_of_interpreter(line) {
+			return Some(extension);
+		}
+	}
+
+	if let Some(extension) = content
+		.lines()
+		.take(MODELINE_LINES)
+		.find_map(extension_of_modeline)
+	{
+		return Some(extension);
+	}
+
+	match content.trim_start().chars().next() {
+		Some('{') | Some('[') => Some("json"),
+		_ => None,
+	}
+}
+
+/// Returns the extension of the files run by the interpreter of a shebang line,
+/// like `/usr/bin/node` or `/usr/bin/env -S deno run`
+fn extension_of_interpreter(line: &str) -> Option<&'static str> {
+	let mut words = line.split_whitespace().map(|word| {
+		// the interpreter is usually given by its path
+		word.rsplit('/').next().unwrap_or(word)
+	});
+
+	let mut interpreter = words.next()?;
+	if interpreter == "env" {
+		interpreter = words.find(|word| !word.starts_with('-'))?;
+	}
+
+	match interpreter {
+		"node" | "nodejs" | "bun" => Some("js"),
+		"deno" | "ts-node" | "tsx" => Some("ts"),
+		_ => None,
+	}
+}
+
+/// Returns the extension of the file type set by a Vim modeline (`vim: set ft=javascript:`)
+/// or an Emacs one (`-*- mode: js -*-`) in the line
+fn extension_of_modeline(line: &str) -> Option<&'static str> {
+	let file_type = if let Some(index) = line.find("-*-") {
+		let variables = &line[index + 3..];
+		let variables = &variables[..variables.find("-*-")?];
+		variables.split(';').find_map(|variable| {
+			let (name, value) = variable.split_once(':')?;
+			(name.trim() == "mode").then(|| value.trim())
+		})?
+	} else {
+		let index = line.find("vim:").or_else(|| line.find("vi:"))?;
+		let options = &line[index..];
+		options
+			.split(|c: char| c.is_whitespace() || c == ':')
+			.find_map(|option| {
+				option
+					.strip_prefix("ft=")
+					.or_else(|| option.strip_prefix("filetype="))
+			})?
+	};
+
+	match file_type.to_ascii_lowercase().as_str() {
+		"javascript" | "js" | "js2" => Some("js"),
+		"javascriptreact" | "jsx" | "rjsx" => Some("jsx"),
+		"typescript" | "ts" => Some("ts"),
+		"typescriptreact" | "tsx" => Some("tsx"),
+		"json" => Some("json"),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::detect_extension;
+
+	#[test]
+	fn detects_the_interpreter_of_the_shebang() {
+		assert_eq!(detect_extension("#!/usr/bin/env node\nmain()"), Some("js"));
+		assert_eq!(
+			detect_extension("#!/usr/local/bin/node --harmony"),
+			Some("js")
+		);
+		assert_eq!(
+			detect_extension("#!/usr/bin/env -S deno run --allow-net"),
+			Some("ts")
+		);
+		assert_eq!(detect_extension("#!/bin/sh\necho {}"), None);
+	}
+
+	#[test]
+	fn detects_the_file_type_of_the_modeline() {
+		assert_eq!(
+			detect_extension("#!/opt/runner\n// vim: set ft=typescript:\n"),
+			Some("ts")
+		);
+		assert_eq!(detect_extension("/* vi: filetype=json */\n"), Some("json"));
+		assert_eq!(
+			detect_extension("// -*- mode: js; indent-tabs-mode: t -*-\n"),
+			Some("js")
+		);
+		assert_eq!(detect_extension("// vim: set ft=python:\n"), None);
+	}
+
+	#[test]
+	fn sniffs_json_documents() {
+		assert_eq!(detect_extension("\n  { \"a\": 1 }"), Some("json"));
+		assert_eq!(detect_extension("\u{feff}[1, 2]"), Some("json"));
+		assert_eq!(detect_extension("let a = {}"), None);
+		assert_eq!(detect_extension(""), None);
+	}
+}
@@ -1,7 +1,25 @@
-use super::{ExtensionHandler, Mime};
+use super::{ExtensionHandler, Language, Mime, Parsed, SourceKind, SourceType};
+use rome_formatter::{FormatError, FormatOptions, FormatResult, Formatted};
+use rslint_parser::parse;
 
+/// The handler of the JavaScript and TypeScript files: scripts, ES modules, TypeScript files
+/// and their JSX variants
 #[derive(Debug, PartialEq, Eq)]
-pub struct JsFileHandler {}
+pub struct JsFileHandler {
+	source_type: SourceType,
+}
+
+impl JsFileHandler {
+	pub fn new(source_type: SourceType) -> Self {
+		Self { source_type }
+	}
+}
+
+impl Default for JsFileHandler {
+	fn default() -> Self {
+		Self::new(SourceType::script())
+	}
+}
 
 impl ExtensionHandler for JsFileHandler {
 	fn capabilities(&self) -> super::Capabilities {
@@ -11,14 +29,39 @@ impl ExtensionHandler for JsFileHandler {
 		}
 	}
 
-	fn language(&self) -> super::Language {
-		super::Language::Js
+	fn language(&self) -> Language {
+		match self.source_type.kind {
+			SourceKind::TypeScript => Language::Ts,
+			SourceKind::Script | SourceKind::Module => Language::Js,
+		}
 	}
 
-	fn mime(&self) -> super::Mime {
+	fn mime(&self) -> Mime {
 		Mime::Javascript
 	}
 
+	fn source_type(&self) -> Option<SourceType> {
+		Some(self.source_type)
+	}
+
+	fn parse(&self, text: &str) -> Option<Parsed> {
+		let parse = parse(text, 0, self.source_type.syntax());
+		Some(Parsed {
+			root: parse.syntax(),
+			diagnostics: parse.errors().to_vec(),
+		})
+	}
+
+	fn format(&self, text: &str, options: FormatOptions) -> FormatResult<Formatted> {
+		// the parser doesn't support JSX yet, the files are reported instead of being mangled
+		if self.source_type.jsx {
+			return Err(FormatError::UnsupportedLanguage);
+		}
+
+		let parse = parse(text, 0, self.source_type.syntax());
+		rome_formatter::format(&parse, options)
+	}
+
 	fn may_use_tabs(&self) -> bool {
 		true
 	}
@@ -1,4 +1,6 @@
 use super::{ExtensionHandler, Mime};
+use rome_formatter::{format_json, FormatOptions, FormatResult, Formatted};
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct JsonFileHandler {}
 
@@ -21,4 +23,8 @@ impl ExtensionHandler for JsonFileHandler {
 	fn may_use_tabs(&self) -> bool {
 		true
 	}
+
+	fn format(&self, text: &str, options: FormatOptions) -> FormatResult<Formatted> {
+		Ok(format_json(text, options))
+	}
 }
@@ -1,7 +1,12 @@
+use rome_formatter::{FormatError, FormatOptions, FormatResult, Formatted};
+use rslint_errors::Diagnostic;
+use rslint_parser::{FileKind, Syntax, SyntaxNode};
+
 pub mod javascript;
 pub mod json;
 pub mod unknown;
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Language {
 	Js,
 	Json,
@@ -27,6 +32,69 @@ impl std::fmt::Display for Mime {
 	}
 }
 
+/// How the content of a JavaScript or TypeScript file must be parsed
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SourceKind {
+	Script,
+	Module,
+	TypeScript,
+}
+
+/// The flavor of a JavaScript or TypeScript file, deduced from its extension
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SourceType {
+	pub kind: SourceKind,
+
+	/// Whether the file can contain JSX elements
+	pub jsx: bool,
+}
+
+impl SourceType {
+	pub const fn script() -> Self {
+		Self {
+			kind: SourceKind::Script,
+			jsx: false,
+		}
+	}
+
+	pub const fn module() -> Self {
+		Self {
+			kind: SourceKind::Module,
+			jsx: false,
+		}
+	}
+
+	pub const fn typescript() -> Self {
+		Self {
+			kind: SourceKind::TypeScript,
+			jsx: false,
+		}
+	}
+
+	pub const fn with_jsx(mut self) -> Self {
+		self.jsx = true;
+		self
+	}
+
+	/// Returns the [Syntax] used to parse a file of this source type
+	pub fn syntax(self) -> Syntax {
+		// the parser doesn't support JSX yet, so it's parsed as the flavor it extends
+		let file_kind = match self.kind {
+			SourceKind::Script => FileKind::Script,
+			SourceKind::Module => FileKind::Module,
+			SourceKind::TypeScript => FileKind::TypeScript,
+		};
+		Syntax::new(file_kind)
+	}
+}
+
+/// The syntax tree of a file, with the diagnostics reported by its parser
+#[derive(Debug, Clone)]
+pub struct Parsed {
+	pub root: SyntaxNode,
+	pub diagnostics: Vec<Diagnostic>,
+}
+
 #[derive(Debug)]
 pub struct Capabilities {
 	pub lint: bool,
@@ -34,7 +102,9 @@ pub struct Capabilities {
 }
 
 /// Main trait to use to add a new language to Rome
-pub trait ExtensionHandler {
+///
+/// Handlers are shared by the threads that process the files, so they must be [Send] and [Sync].
+pub trait ExtensionHandler: Send + Sync {
 	/// The language of the file. It can be a super language.
 	/// For example, a ".js" file can have [Language::Ts]
 	fn language(&self) -> Language;
@@ -42,6 +112,11 @@ pub trait ExtensionHandler {
 	/// MIME types used to identify a certain language
 	fn mime(&self) -> Mime;
 
+	/// How the file must be parsed, for the languages that have different flavors
+	fn source_type(&self) -> Option<SourceType> {
+		None
+	}
+
 	/// A file that can support tabs inside its content
 	fn may_use_tabs(&self) -> bool {
 		true
@@ -55,6 +130,24 @@ pub trait ExtensionHandler {
 		}
 	}
 
+	/// Parses the content of a file.
+	///
+	/// Returns [None] if the language has no syntax tree
+	fn parse(&self, _text: &str) -> Option<Parsed> {
+		None
+	}
+
+	/// Formats the content of a file
+	fn format(&self, _text: &str, _options: FormatOptions) -> FormatResult<Formatted> {
+		Err(FormatError::UnsupportedLanguage)
+	}
+
+	/// The diagnostics of the content of a file, by default the ones of its parser
+	fn diagnostics(&self, text: &str) -> Vec<Diagnostic> {
+		self.parse(text)
+			.map_or_else(Vec::new, |parsed| parsed.diagnostics)
+	}
+
 	/// How a file should be treated. Usually an asset doesn't posses a parser.
 	///
 	/// An image should me parked as asset.
@@ -1,27 +1,59 @@
 use crate::file_handlers::{javascript::JsFileHandler, unknown::UnknownFileHandler};
-use file_handlers::{json::JsonFileHandler, ExtensionHandler};
+use file_handlers::{json::JsonFileHandler, ExtensionHandler, Parsed, SourceType};
+use rome_formatter::{FormatError, FormatOptions, FormatResult, Formatted, SourceMarker};
+use rslint_errors::Diagnostic;
+use rslint_parser::TextSize;
 use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
 
+mod detection;
 pub mod file_handlers;
+pub mod workspace;
 
-// these strings will live for the whole App, so it makes sense to have them as static
-pub type Handlers = HashMap<&'static str, Box<dyn ExtensionHandler>>;
+/// The byte order mark that may start a UTF-8 file
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// The handlers of the files, by extension or by file name.
+///
+/// The handlers are shared, so that the same handler can be registered for several keys.
+pub type Handlers = HashMap<String, Arc<dyn ExtensionHandler>>;
 
 pub struct App {
+	/// The handlers by extension, without the leading dot
 	handlers: Handlers,
-	unknown_handler: Box<dyn ExtensionHandler>,
+	/// The handlers by file name, like `package.json`, which take precedence over the extensions
+	file_name_handlers: Handlers,
+	unknown_handler: Arc<dyn ExtensionHandler>,
 }
 
 impl Default for App {
 	fn default() -> Self {
-		let mut map: Handlers = HashMap::new();
-		map.insert("js", Box::new(JsFileHandler {}));
-		map.insert("ts", Box::new(JsFileHandler {}));
-		map.insert("json", Box::new(JsonFileHandler {}));
-		Self {
-			handlers: map,
-			unknown_handler: Box::new(UnknownFileHandler {}),
-		}
+		let mut app = Self {
+			handlers: HashMap::new(),
+			file_name_handlers: HashMap::new(),
+			unknown_handler: Arc::new(UnknownFileHandler {}),
+		};
+
+		let script = Arc::new(JsFileHandler::new(SourceType::script()));
+		let typescript = Arc::new(JsFileHandler::new(SourceType::typescript()));
+		// like Node.js, `.js` files are scripts unless they use the `.mjs` extension
+		app.register_extension("js", script.clone());
+		app.register_extension("mjs", Arc::new(JsFileHandler::new(SourceType::module())));
+		app.register_extension("cjs", script);
+		app.register_extension(
+			"jsx",
+			Arc::new(JsFileHandler::new(SourceType::module().with_jsx())),
+		);
+		app.register_extension("ts", typescript.clone());
+		app.register_extension("mts", typescript.clone());
+		app.register_extension("cts", typescript);
+		app.register_extension(
+			"tsx",
+			Arc::new(JsFileHandler::new(SourceType::typescript().with_jsx())),
+		);
+		app.register_extension("json", Arc::new(JsonFileHandler {}));
+		app
 	}
 }
 
@@ -30,16 +62,309 @@ impl App {
 		Default::default()
 	}
 
-	pub fn get_handler<'a>(&self, file_extension: &'a str) -> Option<&dyn ExtensionHandler> {
-		let handler = if self.handlers.contains_key(file_extension) {
-			self.handlers.get(file_extension)
-		} else {
-			Some(&self.unknown_handler)
-		};
-		handler.map(|handler| handler.as_ref())
+	/// Registers the handler of the files with the extension, given without the leading dot.
+	///
+	/// It replaces the handler that was registered for the extension, if any.
+	pub fn register_extension(
+		&mut self,
+		extension: impl Into<String>,
+		handler: Arc<dyn ExtensionHandler>,
+	) {
+		self.handlers.insert(extension.into(), handler);
+	}
+
+	/// Registers the handler of the files with the name, like `package.json` or `.eslintrc`.
+	///
+	/// The handlers registered for a file name take precedence over the ones registered for
+	/// the extension of the file.
+	pub fn register_file_name(
+		&mut self,
+		file_name: impl Into<String>,
+		handler: Arc<dyn ExtensionHandler>,
+	) {
+		self.file_name_handlers.insert(file_name.into(), handler);
+	}
+
+	/// Returns the handler registered for the extension, to register it for other
+	/// extensions or file names
+	pub fn extension_handler(&self, file_extension: &str) -> Option<Arc<dyn ExtensionHandler>> {
+		self.handlers.get(file_extension).cloned()
+	}
+
+	/// Returns the handler of the extension, or the handler of the unknown files
+	pub fn get_handler(&self, file_extension: &str) -> Option<&dyn ExtensionHandler> {
+		let handler = self
+			.handlers
+			.get(file_extension)
+			.unwrap_or(&self.unknown_handler);
+		Some(handler.as_ref())
+	}
+
+	/// Returns the handler of the file: the one registered for its name, or else the one of
+	/// its extension.
+	///
+	/// Returns [None] if the file has no extension and no handler is registered for its name.
+	pub fn get_handler_by_path(&self, path: &Path) -> Option<&dyn ExtensionHandler> {
+		let file_name_handler = path
+			.file_name()
+			.and_then(|file_name| file_name.to_str())
+			.and_then(|file_name| self.file_name_handlers.get(file_name));
+		if let Some(handler) = file_name_handler {
+			return Some(handler.as_ref());
+		}
+
+		// files with an extension that isn't valid UTF-8 have no handler
+		let extension = path.extension()?.to_str()?;
+		self.get_handler(extension)
+	}
+
+	/// Returns the handler of the file like [App::get_handler_by_path] and, when the file has
+	/// no extension, falls back to the language detected from its content: its shebang,
+	/// its modeline or its first character.
+	pub fn get_handler_by_content(
+		&self,
+		path: &Path,
+		content: &str,
+	) -> Option<&dyn ExtensionHandler> {
+		self.get_handler_by_path(path).or_else(|| {
+			let extension = detection::detect_extension(content)?;
+			self.handlers.get(extension).map(|handler| handler.as_ref())
+		})
+	}
+
+	/// Parses the content of the file with its handler.
+	///
+	/// Returns [None] if the language of the file has no syntax tree
+	pub fn parse(&self, path: &Path, text: &str) -> Option<Parsed> {
+		self.handler_of(path, text).parse(text)
+	}
+
+	/// Formats the content of the file with its handler, if the handler has the format capability.
+	///
+	/// The byte order mark of the content isn't passed to the handler, it's added back at the start
+	/// of the formatted content. The offsets of the sourcemap include it, like the
+	/// [FormatOptions::tracked_offsets].
+	pub fn format(
+		&self,
+		path: &Path,
+		text: &str,
+		mut options: FormatOptions,
+	) -> FormatResult<Formatted> {
+		let handler = self.handler_of(path, text);
+		if !handler.capabilities().format {
+			return Err(FormatError::CapabilityDisabled);
+		}
+
+		match text.strip_prefix(BYTE_ORDER_MARK) {
+			Some(text) => {
+				let bom_len = TextSize::of(BYTE_ORDER_MARK);
+				// an offset inside the byte order mark is tracked as the one after it
+				for offset in &mut options.tracked_offsets {
+					*offset = offset.checked_sub(bom_len).unwrap_or_default();
+				}
+
+				let formatted = handler.format(text, options)?;
+				let code = format!("{}{}", BYTE_ORDER_MARK, formatted.code());
+				let sourcemap = formatted
+					.sourcemap()
+					.iter()
+					.map(|marker| SourceMarker {
+						source: marker.source + bom_len,
+						dest: marker.dest + bom_len,
+					})
+					.collect();
+				Ok(Formatted::with_sourcemap(&code, sourcemap))
+			}
+			None => handler.format(text, options),
+		}
+	}
+
+	/// Returns the diagnostics of the content of the file, such as its syntax errors
+	pub fn diagnostics(&self, path: &Path, text: &str) -> Vec<Diagnostic> {
+		self.handler_of(path, text).diagnostics(text)
+	}
+
+	/// Returns the handler of the file, or the handler of the unknown files
+	fn handler_of(&self, path: &Path, text: &str) -> &dyn ExtensionHandler {
+		self.get_handler_by_content(path, text)
+			.unwrap_or_else(|| self.unknown_handler.as_ref())
 	}
 }
 
 pub fn create_app() -> App {
 	App::new()
 }
+
+#[cfg(test)]
+mod test {
+	use super::App;
+	use crate::file_handlers::{javascript::JsFileHandler, Language, SourceType};
+	use rome_formatter::{FormatError, FormatOptions, LineEndingStyle};
+	use rslint_parser::TextSize;
+	use std::path::Path;
+	use std::sync::Arc;
+
+	#[test]
+	fn deduces_the_source_type_from_the_extension() {
+		let app = App::new();
+		let source_type = |extension| app.get_handler(extension).unwrap().source_type();
+
+		assert_eq!(source_type("js"), Some(SourceType::script()));
+		assert_eq!(source_type("mjs"), Some(SourceType::module()));
+		assert_eq!(source_type("cjs"), Some(SourceType::script()));
+		assert_eq!(source_type("jsx"), Some(SourceType::module().with_jsx()));
+		assert_eq!(source_type("mts"), Some(SourceType::typescript()));
+		assert_eq!(
+			source_type("tsx"),
+			Some(SourceType::typescript().with_jsx())
+		);
+		assert_eq!(source_type("json"), None);
+		assert_eq!(app.get_handler("cts").unwrap().language(), Language::Ts);
+		assert_eq!(app.get_handler("tsx").unwrap().language(), Language::Ts);
+		assert_eq!(app.get_handler("jsx").unwrap().language(), Language::Js);
+	}
+
+	#[test]
+	fn drives_the_handler_of_the_file() {
+		let app = App::new();
+
+		let formatted = app
+			.format(Path::new("a.js"), "let a =   1", FormatOptions::default())
+			.unwrap();
+		assert_eq!(formatted.code(), "let a = 1;\n");
+
+		let formatted = app
+			.format(
+				Path::new("a.json"),
+				"{ \"a\":   1 }",
+				FormatOptions::default(),
+			)
+			.unwrap();
+		assert_eq!(formatted.code(), "{\"a\": 1}\n");
+
+		assert_eq!(
+			app.format(Path::new("a.md"), "# a", FormatOptions::default()),
+			Err(FormatError::CapabilityDisabled)
+		);
+
+		assert!(app.diagnostics(Path::new("a.js"), "let a =   1").is_empty());
+		assert_eq!(app.diagnostics(Path::new("a.js"), "export {}").len(), 1);
+		assert!(app.parse(Path::new("a.mjs"), "export {}").is_some());
+		assert!(app.parse(Path::new("a.json"), "{}").is_none());
+	}
+
+	#[test]
+	fn reports_the_jsx_files_as_unsupported() {
+		let app = App::new();
+
+		assert_eq!(
+			app.format(Path::new("a.jsx"), "<div />", FormatOptions::default()),
+			Err(FormatError::UnsupportedLanguage)
+		);
+		assert_eq!(
+			app.format(Path::new("a.tsx"), "<div />", FormatOptions::default()),
+			Err(FormatError::UnsupportedLanguage)
+		);
+	}
+
+	#[test]
+	fn registers_handlers_at_runtime() {
+		let mut app = App::new();
+		let json = app.extension_handler("json").unwrap();
+		app.register_extension("jsonc", json.clone());
+		app.register_file_name(".babelrc", json);
+		app.register_extension("js", Arc::new(JsFileHandler::new(SourceType::module())));
+
+		let handler = |path| app.get_handler_by_path(Path::new(path)).unwrap();
+		assert_eq!(handler("a.jsonc").language(), Language::Json);
+		assert_eq!(handler("src/.babelrc").language(), Language::Json);
+		assert_eq!(handler("a.js").source_type(), Some(SourceType::module()));
+		assert_eq!(handler("a.es6").language(), Language::Unknown);
+		assert!(app.get_handler_by_path(Path::new(".eslintrc")).is_none());
+	}
+
+	#[test]
+	fn detects_the_language_of_the_files_without_extension() {
+		let app = App::new();
+		let handler = |path, content| app.get_handler_by_content(Path::new(path), content);
+
+		let deploy = handler("bin/deploy", "#!/usr/bin/env node\n").unwrap();
+		assert_eq!(deploy.source_type(), Some(SourceType::script()));
+		assert_eq!(handler("data", "{}").unwrap().language(), Language::Json);
+		assert_eq!(handler("a.md", "{}").unwrap().language(), Language::Unknown);
+		assert!(handler("Makefile", "all:\n").is_none());
+
+		let formatted = app
+			.format(
+				Path::new("bin/deploy"),
+				"#!/usr/bin/env node\nlet a =   1",
+				FormatOptions::default(),
+			)
+			.unwrap();
+		assert_eq!(formatted.code(), "#!/usr/bin/env node\nlet a = 1;\n");
+	}
+
+	#[test]
+	fn keeps_the_byte_order_mark_and_the_line_endings() {
+		let app = App::new();
+		let options = FormatOptions {
+			line_ending: LineEndingStyle::Auto,
+			..FormatOptions::default()
+		};
+
+		let formatted = app
+			.format(
+				Path::new("a.js"),
+				"\u{feff}let a =   1;\r\nlet b = 2",
+				options,
+			)
+			.unwrap();
+		assert_eq!(formatted.code(), "\u{feff}let a = 1;\r\nlet b = 2;\r\n");
+	}
+
+	#[test]
+	fn tracks_the_offsets_after_the_byte_order_mark() {
+		let app = App::new();
+		let options = FormatOptions {
+			tracked_offsets: vec![TextSize::from(3), TextSize::from(13)],
+			..FormatOptions::default()
+		};
+
+		let formatted = app
+			.format(Path::new("a.js"), "\u{feff}let a =   1;", options)
+			.unwrap();
+		assert_eq!(formatted.code(), "\u{feff}let a = 1;\n");
+		assert_eq!(formatted.offset(TextSize::from(3)), Some(TextSize::from(3)));
+		assert_eq!(
+			formatted.offset(TextSize::from(13)),
+			Some(TextSize::from(11))
+		);
+	}
+
+	#[test]
+	fn formats_the_sources_with_syntax_errors_only_when_allowed() {
+		let app = App::new();
+		let text = "let a =   1;\nif test {   a }\nlet b =   2";
+
+		match app.format(Path::new("a.js"), text, FormatOptions::default()) {
+			Err(FormatError::SyntaxErrors(errors)) => assert!(!errors.is_empty()),
+			result => panic!("expected the syntax errors, got {:?}", result),
+		}
+
+		let options = FormatOptions {
+			format_with_errors: true,
+			..FormatOptions::default()
+		};
+		let formatted = app.format(Path::new("a.js"), text, options).unwrap();
+		assert_eq!(
+			formatted.code(),
+			"let a = 1;\nif test {   a }\nlet b = 2;\n"
+		);
+	}
+
+	#[test]
+	fn app_is_shareable_across_threads() {
+		fn assert_send_sync<T: Send + Sync>() {}
+		assert_send_sync::<App>();
+	}
+}
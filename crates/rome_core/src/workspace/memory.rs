@@ -0,0 +1,37 @@
+use super::FileSystem;
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// A file system that only lives in memory, for the tests and the editors
+#[derive(Debug, Default)]
+pub struct MemoryFileSystem {
+	files: RwLock<HashMap<PathBuf, String>>,
+}
+
+impl MemoryFileSystem {
+	/// Creates or replaces a file
+	pub fn insert(&self, path: impl Into<PathBuf>, content: impl Into<String>) {
+		self.files
+			.write()
+			.unwrap()
+			.insert(path.into(), content.into());
+	}
+}
+
+impl FileSystem for MemoryFileSystem {
+	fn read(&self, path: &Path) -> io::Result<String> {
+		self.files
+			.read()
+			.unwrap()
+			.get(path)
+			.cloned()
+			.ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "the file doesn't exist"))
+	}
+
+	fn write(&self, path: &Path, content: &str) -> io::Result<()> {
+		self.insert(path, content);
+		Ok(())
+	}
+}
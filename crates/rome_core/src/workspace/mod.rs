@@ -0,0 +1,189 @@
+//! The files processed by Rome, as seen by an editor or a test.
+//!
+//! A [Workspace] holds the documents opened in an editor, with their unsaved content and their
+//! version, and reads and writes the other files through a [FileSystem]: the one of the OS,
+//! [OsFileSystem], or one that lives in memory, [MemoryFileSystem].
+use crate::file_handlers::Parsed;
+use crate::App;
+use rome_formatter::{FormatOptions, FormatResult, Formatted};
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+mod memory;
+mod os;
+
+pub use memory::MemoryFileSystem;
+pub use os::OsFileSystem;
+
+/// Where the content of the files is read from and written to.
+///
+/// A file system is shared by the threads that process the files, so it must be [Send] and [Sync].
+pub trait FileSystem: Send + Sync {
+	/// Returns the content of the file
+	fn read(&self, path: &Path) -> io::Result<String>;
+
+	/// Replaces the content of the file, creating it if it doesn't exist
+	fn write(&self, path: &Path, content: &str) -> io::Result<()>;
+}
+
+/// A file opened in an editor, whose content may differ from the one saved on the file system
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Document {
+	pub content: String,
+
+	/// The version given by the editor, which increases with each change
+	pub version: i32,
+}
+
+pub struct Workspace {
+	file_system: Box<dyn FileSystem>,
+	documents: HashMap<PathBuf, Document>,
+}
+
+impl Workspace {
+	pub fn new(file_system: Box<dyn FileSystem>) -> Self {
+		Self {
+			file_system,
+			documents: HashMap::new(),
+		}
+	}
+
+	/// Returns the file system of the files that aren't open
+	pub fn file_system(&self) -> &dyn FileSystem {
+		self.file_system.as_ref()
+	}
+
+	/// Opens a document, replacing the document that was open at the same path
+	pub fn open_document(&mut self, path: impl Into<PathBuf>, content: String, version: i32) {
+		self.documents
+			.insert(path.into(), Document { content, version });
+	}
+
+	/// Replaces the content of an open document.
+	///
+	/// Returns `false`, and ignores the change, if the document isn't open or if the version
+	/// isn't newer than the one of the document.
+	pub fn change_document(&mut self, path: &Path, content: String, version: i32) -> bool {
+		match self.documents.get_mut(path) {
+			Some(document) if version > document.version => {
+				*document = Document { content, version };
+				true
+			}
+			_ => false,
+		}
+	}
+
+	/// Closes a document, without saving it. Returns the document, if it was open
+	pub fn close_document(&mut self, path: &Path) -> Option<Document> {
+		self.documents.remove(path)
+	}
+
+	/// Returns the document open at the path, if any
+	pub fn document(&self, path: &Path) -> Option<&Document> {
+		self.documents.get(path)
+	}
+
+	/// Returns the content of the file: the content of its document if it's open, else the one
+	/// saved on the file system
+	pub fn read(&self, path: &Path) -> io::Result<String> {
+		match self.documents.get(path) {
+			Some(document) => Ok(document.content.clone()),
+			None => self.file_system.read(path),
+		}
+	}
+
+	/// Writes the content to the file system. If the file is open, the content of its document
+	/// is replaced too and its version is increased.
+	pub fn write(&mut self, path: &Path, content: &str) -> io::Result<()> {
+		self.file_system.write(path, content)?;
+		if let Some(document) = self.documents.get_mut(path) {
+			document.content = content.to_string();
+			document.version += 1;
+		}
+		Ok(())
+	}
+
+	/// Writes the content of an open document to the file system.
+	///
+	/// Returns an error of kind [io::ErrorKind::NotFound] if the document isn't open.
+	pub fn save_document(&self, path: &Path) -> io::Result<()> {
+		let document = self
+			.documents
+			.get(path)
+			.ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "the document isn't open"))?;
+		self.file_system.write(path, &document.content)
+	}
+
+	/// Parses the content of the file, see [Workspace::read] and [App::parse]
+	pub fn parse(&self, app: &App, path: &Path) -> io::Result<Option<Parsed>> {
+		let content = self.read(path)?;
+		Ok(app.parse(path, &content))
+	}
+
+	/// Formats the content of the file, see [Workspace::read] and [App::format].
+	///
+	/// Nothing is written, the formatted content can be saved with [Workspace::write].
+	pub fn format(
+		&self,
+		app: &App,
+		path: &Path,
+		options: FormatOptions,
+	) -> FormatResult<Formatted> {
+		let content = self.read(path)?;
+		app.format(path, &content, options)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::{Document, MemoryFileSystem, Workspace};
+	use crate::App;
+	use rome_formatter::FormatOptions;
+	use std::path::Path;
+
+	#[test]
+	fn documents_take_precedence_over_the_file_system() {
+		let file_system = MemoryFileSystem::default();
+		file_system.insert("a.js", "let a =   1");
+		let mut workspace = Workspace::new(Box::new(file_system));
+		let path = Path::new("a.js");
+
+		workspace.open_document(path, String::from("let a =   2"), 1);
+		assert_eq!(workspace.read(path).unwrap(), "let a =   2");
+		assert!(workspace.change_document(path, String::from("let a =   3"), 2));
+		assert!(!workspace.change_document(path, String::from("let a =   4"), 2));
+		assert!(!workspace.change_document(Path::new("b.js"), String::new(), 1));
+
+		let formatted = workspace
+			.format(&App::new(), path, FormatOptions::default())
+			.unwrap();
+		assert_eq!(formatted.code(), "let a = 3;\n");
+		assert_eq!(workspace.file_system().read(path).unwrap(), "let a =   1");
+
+		workspace.write(path, formatted.code()).unwrap();
+		assert_eq!(workspace.file_system().read(path).unwrap(), "let a = 3;\n");
+		assert_eq!(
+			workspace.close_document(path),
+			Some(Document {
+				content: String::from("let a = 3;\n"),
+				version: 3
+			})
+		);
+		assert_eq!(workspace.read(path).unwrap(), "let a = 3;\n");
+	}
+
+	#[test]
+	fn unsaved_documents_can_be_saved() {
+		let mut workspace = Workspace::new(Box::new(MemoryFileSystem::default()));
+		let path = Path::new("new.json");
+
+		assert!(workspace.read(path).is_err());
+		assert!(workspace.save_document(path).is_err());
+
+		workspace.open_document(path, String::from("{}"), 0);
+		assert!(workspace.parse(&App::new(), path).unwrap().is_none());
+		workspace.save_document(path).unwrap();
+		assert_eq!(workspace.file_system().read(path).unwrap(), "{}");
+	}
+}
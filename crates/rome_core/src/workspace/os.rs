@@ -0,0 +1,130 @@
+use super::FileSystem;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Distinguishes the temporary files created at the same time by the threads of the process
+static TEMPORARY_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// The file system of the operating system
+#[derive(Debug, Default)]
+pub struct OsFileSystem;
+
+impl FileSystem for OsFileSystem {
+	fn read(&self, path: &Path) -> io::Result<String> {
+		fs::read_to_string(path)
+	}
+
+	/// Writes the content to a temporary file next to the file, and then renames it into place,
+	/// so that the file is never left half written.
+	///
+	/// A symbolic link is resolved, and its target is written. The permissions of the file are
+	/// kept and, when the content is unchanged, the file isn't written at all.
+	fn write(&self, path: &Path, content: &str) -> io::Result<()> {
+		let target = match fs::canonicalize(path) {
+			Ok(target) => target,
+			Err(error) if error.kind() == io::ErrorKind::NotFound => path.to_path_buf(),
+			Err(error) => return Err(error),
+		};
+
+		let permissions = match fs::metadata(&target) {
+			Ok(metadata) => {
+				if fs::read(&target)? == content.as_bytes() {
+					return Ok(());
+				}
+				Some(metadata.permissions())
+			}
+			Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+			Err(error) => return Err(error),
+		};
+
+		let temporary = temporary_sibling(&target);
+		let result = write_new_file(&temporary, content).and_then(|_| {
+			if let Some(permissions) = permissions {
+				fs::set_permissions(&temporary, permissions)?;
+			}
+			fs::rename(&temporary, &target)
+		});
+
+		if result.is_err() {
+			let _ = fs::remove_file(&temporary);
+		}
+		result
+	}
+}
+
+/// Returns the path of a temporary file in the directory of the file, so that it can be renamed
+/// to the file without copying it across file systems
+fn temporary_sibling(path: &Path) -> PathBuf {
+	let file_name = path
+		.file_name()
+		.map(|file_name| file_name.to_string_lossy())
+		.unwrap_or_default();
+	let id = TEMPORARY_FILES.fetch_add(1, Ordering::Relaxed);
+	path.with_file_name(format!(".{}.{}-{}.tmp", file_name, process::id(), id))
+}
+
+/// Writes the content to a file that doesn't exist yet and flushes it to the disk
+fn write_new_file(path: &Path, content: &str) -> io::Result<()> {
+	let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+	file.write_all(content.as_bytes())?;
+	file.sync_all()
+}
+
+#[cfg(test)]
+mod test {
+	use super::OsFileSystem;
+	use crate::workspace::FileSystem;
+	use std::env;
+	use std::fs;
+	use std::path::PathBuf;
+
+	fn test_directory(name: &str) -> PathBuf {
+		let path = env::temp_dir().join(format!("rome_core_{}_{}", name, std::process::id()));
+		let _ = fs::remove_dir_all(&path);
+		fs::create_dir_all(&path).unwrap();
+		path
+	}
+
+	#[test]
+	fn writes_the_file_and_leaves_no_temporary_file() {
+		let directory = test_directory("writes_the_file");
+		let path = directory.join("a.js");
+
+		OsFileSystem.write(&path, "let a = 1;\n").unwrap();
+		OsFileSystem.write(&path, "let a = 2;\n").unwrap();
+
+		assert_eq!(OsFileSystem.read(&path).unwrap(), "let a = 2;\n");
+		assert_eq!(fs::read_dir(&directory).unwrap().count(), 1);
+		fs::remove_dir_all(&directory).unwrap();
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn keeps_the_permissions_and_the_symbolic_links() {
+		use std::os::unix::fs::{symlink, MetadataExt, PermissionsExt};
+
+		let directory = test_directory("keeps_the_permissions");
+		let target = directory.join("target.js");
+		let link = directory.join("link.js");
+		fs::write(&target, "let a =   1").unwrap();
+		fs::set_permissions(&target, fs::Permissions::from_mode(0o754)).unwrap();
+		symlink(&target, &link).unwrap();
+
+		let inode = fs::metadata(&target).unwrap().ino();
+		OsFileSystem.write(&link, "let a =   1").unwrap();
+		assert_eq!(fs::metadata(&target).unwrap().ino(), inode);
+
+		OsFileSystem.write(&link, "let a = 1;\n").unwrap();
+		assert!(fs::symlink_metadata(&link)
+			.unwrap()
+			.file_type()
+			.is_symlink());
+		assert_eq!(fs::read_to_string(&target).unwrap(), "let a = 1;\n");
+		let mode = fs::metadata(&target).unwrap().permissions().mode();
+		assert_eq!(mode & 0o777, 0o754);
+		fs::remove_dir_all(&directory).unwrap();
+	}
+}
@@ -6,9 +6,9 @@ use rslint_parser::ast::{
 	JsConstructorParameterList, JsContinueStatement, JsDebuggerStatement, JsDefaultClause,
 	JsDoWhileStatement, JsEmptyStatement, JsExpressionStatement, JsFinallyClause,
 	JsFunctionDeclaration, JsGetterClassMember, JsIdentifierBinding, JsIdentifierExpression,
-	JsIfStatement, JsLabeledStatement, JsNullLiteralExpression, JsNumberLiteralExpression,
-	JsObjectExpression, JsParameterList, JsPropertyClassMember, JsPropertyObjectMember,
-	JsReturnStatement, JsScript, JsSequenceExpression, JsSetterClassMember,
+	JsIfStatement, JsLabeledStatement, JsModule, JsNullLiteralExpression,
+	JsNumberLiteralExpression, JsObjectExpression, JsParameterList, JsPropertyClassMember,
+	JsPropertyObjectMember, JsReturnStatement, JsScript, JsSequenceExpression, JsSetterClassMember,
 	JsShorthandPropertyObjectMember, JsSpread, JsStringLiteralExpression, JsSwitchStatement,
 	JsTryStatement, JsVariableDeclarationStatement, JsVariableDeclarator, JsWhileStatement,
 	JsWithStatement,
@@ -53,6 +53,9 @@ impl ToFormatElement for SyntaxNode {
 			SyntaxKind::JS_PARAMETER_LIST => JsParameterList::cast(self.clone())
 				.unwrap()
 				.to_format_element(formatter),
+			SyntaxKind::JS_MODULE => JsModule::cast(self.clone())
+				.unwrap()
+				.to_format_element(formatter),
 			SyntaxKind::JS_SCRIPT => JsScript::cast(self.clone())
 				.unwrap()
 				.to_format_element(formatter),
@@ -177,10 +180,9 @@ impl ToFormatElement for SyntaxNode {
 				.unwrap()
 				.to_format_element(formatter),
 
-			_ => todo!(
-				"Implement formatting for the {:?} syntax kind.",
-				self.kind()
-			),
+			// the nodes without a dedicated formatting, including the variants of the `JsAny*`
+			// enums that they delegate here, are printed as they are in the source
+			_ => Ok(formatter.format_verbatim(self)),
 		}
 	}
 }
@@ -1,5 +1,6 @@
 use crate::format_elements;
 use crate::intersperse::Intersperse;
+use rslint_parser::TextSize;
 use std::ops::Deref;
 
 type Content = Box<FormatElement>;
@@ -14,7 +15,8 @@ pub fn empty_element() -> FormatElement {
 
 /// A line break that only gets printed if the enclosing [Group] doesn't fit on a single line.
 /// It's omitted if the enclosing [Group] fits on a single line.
-/// A soft line break is identical to a hard line break when not enclosed inside of a [Group].
+/// A soft line break is identical to a hard line break when not enclosed inside of a [Group],
+/// which means that it isn't printed at the start of a line either.
 ///
 /// ## Examples
 ///
@@ -59,6 +61,10 @@ pub const fn soft_line_break() -> FormatElement {
 /// A forced line break that are always printed. A hard line break forces any enclosing [Group]
 /// to be printed over multiple lines.
 ///
+/// A line break at the start of a line isn't printed, so that consecutive line breaks, like the
+/// ones before and after a comment on its own line, are printed as a single one. Use [empty_line]
+/// to print an empty line.
+///
 /// ## Examples
 ///
 /// It forces a line break, even if the enclosing [Group] would otherwise fit on a single line.
@@ -74,12 +80,51 @@ pub const fn soft_line_break() -> FormatElement {
 ///
 /// assert_eq!("a,\nb\n", format_element(&elements, FormatOptions::default()).code());
 /// ```
+///
+/// Consecutive line breaks are printed as a single one
+/// ```
+/// use rome_formatter::{format_element, format_elements, token, FormatOptions, hard_line_break};
+///
+/// let elements = format_elements![
+///   token("a,"),
+///   hard_line_break(),
+///   hard_line_break(),
+///   token("b"),
+/// ];
+///
+/// assert_eq!("a,\nb", format_element(&elements, FormatOptions::default()).code());
+/// ```
 #[inline]
 pub const fn hard_line_break() -> FormatElement {
 	FormatElement::Line(Line::new(LineMode::Hard))
 }
 
+/// A forced line break followed by an empty line. The consecutive empty lines are printed as a
+/// single one.
+///
+/// ## Examples
+///
+/// ```
+/// use rome_formatter::{format_element, format_elements, token, FormatOptions, empty_line, hard_line_break};
+///
+/// let elements = format_elements![
+///   token("a,"),
+///   empty_line(),
+///   empty_line(),
+///   token("b"),
+///   hard_line_break()
+/// ];
+///
+/// assert_eq!("a,\n\nb\n", format_element(&elements, FormatOptions::default()).code());
+/// ```
+#[inline]
+pub const fn empty_line() -> FormatElement {
+	FormatElement::Line(Line::new(LineMode::Empty))
+}
+
 /// A line break if the enclosing [Group] doesn't fit on a single line, a space otherwise.
+/// Like a [hard_line_break], the line break isn't printed at the start of a line, and like a
+/// [space_token], the space isn't printed after another space.
 ///
 /// ## Examples
 ///
@@ -157,6 +202,9 @@ pub fn token<S: Into<String>>(text: S) -> FormatElement {
 
 /// Inserts a single space. Allows to separate different tokens.
 ///
+/// The space is only printed before a token: consecutive spaces are printed as a single one, and
+/// the spaces before a line break are omitted.
+///
 /// ## Examples
 ///
 /// ```
@@ -167,6 +215,15 @@ pub fn token<S: Into<String>>(text: S) -> FormatElement {
 ///
 /// assert_eq!("a b", format_element(&elements, FormatOptions::default()).code());
 /// ```
+///
+/// Consecutive spaces are printed as a single one
+/// ```
+/// use rome_formatter::{FormatOptions, token, format_element, space_token, format_elements};
+///
+/// let elements = format_elements![token("a"), space_token(), space_token(), token("b")];
+///
+/// assert_eq!("a b", format_element(&elements, FormatOptions::default()).code());
+/// ```
 #[inline]
 pub const fn space_token() -> FormatElement {
 	FormatElement::Space
@@ -576,6 +633,67 @@ where
 	}
 }
 
+/// Content that is printed at the end of the line, right before the next line break, like a
+/// `// comment` that follows a token. A line suffix forces the enclosing [Group] to break, so that
+/// no content that follows can end up after it on the same line.
+///
+/// ## Examples
+///
+/// ```
+/// use rome_formatter::{format_element, format_elements, token, FormatOptions, hard_line_break, line_suffix, space_token};
+///
+/// let elements = format_elements![
+///   token("a"),
+///   line_suffix(format_elements![space_token(), token("// comment")]),
+///   token(";"),
+///   hard_line_break(),
+/// ];
+///
+/// assert_eq!("a; // comment\n", format_element(&elements, FormatOptions::default()).code());
+/// ```
+#[inline]
+pub fn line_suffix<T: Into<FormatElement>>(content: T) -> FormatElement {
+	let content = content.into();
+
+	if content.is_empty() {
+		content
+	} else {
+		FormatElement::LineSuffix(LineSuffix::new(content))
+	}
+}
+
+/// Marks the position of an offset of the source in the printed code: the printer reports where
+/// it ended up with the [crate::SourceMarker]s of [crate::Formatted]. The position is the one of
+/// the next printed token, after the space or indention that precedes it.
+///
+/// ## Examples
+///
+/// ```
+/// use rome_formatter::{format_element, format_elements, source_position, space_token, token, FormatOptions, SourceMarker};
+/// use rslint_parser::TextSize;
+///
+/// // formats `a=b`
+/// let elements = format_elements![
+///   token("a"),
+///   space_token(),
+///   token("="),
+///   space_token(),
+///   source_position(TextSize::from(2)),
+///   token("b"),
+/// ];
+///
+/// let formatted = format_element(&elements, FormatOptions::default());
+/// assert_eq!("a = b", formatted.code());
+/// assert_eq!(
+///   &[SourceMarker { source: TextSize::from(2), dest: TextSize::from(4) }],
+///   formatted.sourcemap()
+/// );
+/// ```
+#[inline]
+pub const fn source_position(offset: TextSize) -> FormatElement {
+	FormatElement::SourcePosition(offset)
+}
+
 /// Language agnostic IR for formatting source code.
 ///
 /// Use the helper functions like [space], [soft_line_break] etc. defined in this file to create elements.
@@ -608,6 +726,12 @@ pub enum FormatElement {
 
 	/// A token that should be printed as is, see [token] for documentation and examples.
 	Token(Token),
+
+	/// Content printed at the end of the line, see [line_suffix] for documentation and examples.
+	LineSuffix(LineSuffix),
+
+	/// The position of a source offset in the printed code, see [source_position] for documentation.
+	SourcePosition(TextSize),
 }
 
 /// Inserts a new line
@@ -630,6 +754,8 @@ pub enum LineMode {
 	Soft,
 	/// See [hard_line_break] for documentation.
 	Hard,
+	/// See [empty_line] for documentation.
+	Empty,
 }
 
 /// Increases the indention by one; see [indented_with_soft_break] and [indented_with_hard_break].
@@ -708,6 +834,20 @@ impl ConditionalGroupContent {
 	}
 }
 
+/// See [line_suffix] for documentation
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LineSuffix {
+	pub(crate) content: Content,
+}
+
+impl LineSuffix {
+	pub fn new(content: FormatElement) -> Self {
+		Self {
+			content: Box::new(content),
+		}
+	}
+}
+
 /// See [token] for documentation
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Token(String);
@@ -766,6 +906,7 @@ impl FormatElement {
 				FormatElement::List(List::new(content))
 			}
 			FormatElement::Token(s) => token(s.trim_start()),
+			FormatElement::LineSuffix(_) | FormatElement::SourcePosition(_) => self.clone(),
 		}
 	}
 
@@ -810,6 +951,7 @@ impl FormatElement {
 				}
 			}
 			FormatElement::Token(s) => token(s.trim_end()),
+			FormatElement::LineSuffix(_) | FormatElement::SourcePosition(_) => self.clone(),
 		}
 	}
 }
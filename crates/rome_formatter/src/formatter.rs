@@ -1,10 +1,16 @@
-use crate::printer::Printer;
+use crate::printer::{Printer, PrinterOptions};
 use crate::{
-	concat_elements, format_elements, if_group_breaks, token, FormatElement, FormatOptions,
-	FormatResult, Formatted, ToFormatElement,
+	concat_elements, empty_element, empty_line, format_elements, hard_line_break, if_group_breaks,
+	indent, join_elements, line_suffix, source_position, space_token, token, FormatElement,
+	FormatOptions, FormatResult, Formatted, LineEndingStyle, SourceMarker, ToFormatElement,
 };
+use rome_rowan::api::{SyntaxTrivia, SyntaxTriviaPiece};
 use rome_rowan::SyntaxElement;
-use rslint_parser::{AstNode, AstSeparatedList, SyntaxNode, SyntaxToken};
+use rslint_parser::ast::JsAnyModuleItem;
+use rslint_parser::{
+	AstNode, AstSeparatedList, JsLanguage, SyntaxNode, SyntaxToken, TextRange, TextSize, T,
+};
+use rslint_text_edit::TextEdit;
 
 /// Handles the formatting of a CST and stores the options how the CST should be formatted (user preferences).
 /// The formatter is passed to the [ToFormatElement] implementation of every node in the CST so that they
@@ -12,12 +18,31 @@ use rslint_parser::{AstNode, AstSeparatedList, SyntaxNode, SyntaxToken};
 #[derive(Debug, Default)]
 pub struct Formatter {
 	options: FormatOptions,
+	/// The sorted source offsets to report the position of in the formatted code
+	tracked_offsets: Vec<TextSize>,
+	/// The ranges of the syntax errors of the source
+	syntax_errors: Vec<TextRange>,
 }
 
 impl Formatter {
 	/// Creates a new context that uses the given formatter options
-	pub fn new(options: FormatOptions) -> Self {
-		Self { options }
+	pub fn new(mut options: FormatOptions) -> Self {
+		let mut tracked_offsets = std::mem::take(&mut options.tracked_offsets);
+		tracked_offsets.sort_unstable();
+		tracked_offsets.dedup();
+
+		Self {
+			options,
+			tracked_offsets,
+			syntax_errors: Vec::new(),
+		}
+	}
+
+	/// Sets the ranges of the syntax errors of the source, so that the nodes that contain one of
+	/// them and can't be formatted are printed as they are in the source
+	pub(crate) fn with_syntax_errors(mut self, syntax_errors: Vec<TextRange>) -> Self {
+		self.syntax_errors = syntax_errors;
+		self
 	}
 
 	/// Returns the [FormatOptions] specifying how to format the current CST
@@ -27,16 +52,78 @@ impl Formatter {
 	}
 
 	/// Formats a CST
-	pub fn format_root(self, root: &SyntaxNode) -> FormatResult<Formatted> {
+	pub fn format_root(mut self, root: &SyntaxNode) -> FormatResult<Formatted> {
 		let element = self.format_syntax_node(root)?;
 
+		if self.options.line_ending == LineEndingStyle::Auto {
+			self.options.line_ending = detect_line_ending(root);
+		}
 		let printer = Printer::new(self.options);
-		Ok(printer.print(&element))
+		let formatted = printer.print(&element);
+		let sourcemap = resolve_tracked_offsets(
+			&self.tracked_offsets,
+			formatted.sourcemap(),
+			TextSize::of(formatted.code().as_str()),
+		);
+		Ok(Formatted::with_sourcemap(formatted.code(), sourcemap))
+	}
+
+	/// Formats the smallest statement that contains the range, or the statements of a list that
+	/// intersect with it, and returns the edit that replaces them with their formatted code.
+	///
+	/// The statements are indented like the line where the first one starts. The edit is empty if
+	/// the range isn't inside a statement, like a range in the comments at the end of the file.
+	pub fn format_range(mut self, root: &SyntaxNode, range: TextRange) -> FormatResult<TextEdit> {
+		if self.options.line_ending == LineEndingStyle::Auto {
+			self.options.line_ending = detect_line_ending(root);
+		}
+
+		let range = range
+			.intersect(root.text_range())
+			.unwrap_or_else(|| TextRange::empty(root.text_range().end()));
+		let items = match covering_items(root, range) {
+			Some(items) => items,
+			None => return Ok(TextEdit::default()),
+		};
+
+		let (first_token, last_token) = match (
+			items.first().and_then(|item| item.syntax().first_token()),
+			items.last().and_then(|item| item.syntax().last_token()),
+		) {
+			(Some(first_token), Some(last_token)) => (first_token, last_token),
+			_ => return Ok(TextEdit::default()),
+		};
+		// the comments of the items are formatted with them
+		let replaced_range = TextRange::new(
+			comments_range(&first_token.leading_trivia())
+				.map_or(first_token.text_trimmed_range().start(), TextRange::start),
+			comments_range(&last_token.trailing_trivia())
+				.map_or(last_token.text_trimmed_range().end(), TextRange::end),
+		);
+
+		let element = join_elements(
+			hard_line_break(),
+			items
+				.into_iter()
+				.map(|item| self.format_node(item))
+				.collect::<FormatResult<Vec<_>>>()?,
+		);
+
+		let options = PrinterOptions::from(self.options);
+		let level = indentation_level(root, replaced_range.start(), &options);
+		let element = (0..level).fold(element, |element, _| indent(element));
+		let formatted = Printer::new(options).print(&element);
+
+		Ok(TextEdit::replace(replaced_range, formatted.code().clone()))
 	}
 
 	fn format_syntax_node(&self, node: &SyntaxNode) -> FormatResult<FormatElement> {
 		let start = self.format_node_start(node);
-		let content = node.to_format_element(self)?;
+		let content = if has_typescript_children(node) {
+			self.format_verbatim(node)
+		} else {
+			self.format_or_verbatim(node, node.to_format_element(self))?
+		};
 		Ok(concat_elements(vec![
 			start,
 			content,
@@ -46,38 +133,82 @@ impl Formatter {
 
 	/// Recursively formats the ast node and all its children
 	///
-	/// Returns `None` if the node couldn't be formatted because of syntax errors in its sub tree.
-	/// The parent may use `format_raw` to insert the node content as is.
+	/// A node that can't be formatted because of syntax errors in its sub tree, like a missing
+	/// child, is printed as it is in the source, see [Formatter::format_verbatim]. The errors of
+	/// the other nodes are returned.
 	pub fn format_node<T: AstNode + ToFormatElement>(
 		&self,
 		node: T,
 	) -> FormatResult<FormatElement> {
+		let content = if has_typescript_children(node.syntax()) {
+			self.format_verbatim(node.syntax())
+		} else {
+			self.format_or_verbatim(node.syntax(), node.to_format_element(self))?
+		};
+
 		Ok(concat_elements(vec![
 			self.format_node_start(node.syntax()),
-			node.to_format_element(self)?,
+			content,
 			self.format_node_end(node.syntax()),
 		]))
 	}
 
+	/// Returns the formatted content of the node, or the node as it is in the source if it
+	/// couldn't be formatted because it contains a syntax error
+	fn format_or_verbatim(
+		&self,
+		node: &SyntaxNode,
+		content: FormatResult<FormatElement>,
+	) -> FormatResult<FormatElement> {
+		match content {
+			Err(_) if self.has_syntax_errors(node) => Ok(self.format_verbatim(node)),
+			content => content,
+		}
+	}
+
+	/// Returns `true` if the node contains one of the syntax errors of the source
+	fn has_syntax_errors(&self, node: &SyntaxNode) -> bool {
+		let range = node.text_range();
+		self.syntax_errors
+			.iter()
+			.any(|error| range.intersect(*error).is_some())
+	}
+
 	/// Helper function that returns what should be printed before the node that work on
 	/// the non-generic [SyntaxNode] to avoid unrolling the logic for every [AstNode] type.
-	fn format_node_start(&self, _node: &SyntaxNode) -> FormatElement {
-		// TODO: Set the marker for the start source map location, add leading comments, ...
-		concat_elements(vec![])
+	fn format_node_start(&self, node: &SyntaxNode) -> FormatElement {
+		// The comments are attached to the tokens, see [Formatter::format_token]
+		self.format_source_position(node.text_range(), node.text_range().start())
 	}
 
 	/// Helper function that returns what should be printed after the node that work on
 	/// the non-generic [SyntaxNode] to avoid unrolling the logic for every [AstNode] type.
-	fn format_node_end(&self, _node: &SyntaxNode) -> FormatElement {
-		// TODO: Sets the marker for the end source map location, add trailing comments, ...
-		concat_elements(vec![])
+	fn format_node_end(&self, node: &SyntaxNode) -> FormatElement {
+		self.format_source_position(node.text_range(), node.text_range().end())
+	}
+
+	/// Marks the position of the source offset if the range contains a tracked offset, so that
+	/// the tracked offset can be mapped from the closest positions around it
+	fn format_source_position(&self, range: TextRange, offset: TextSize) -> FormatElement {
+		let index = self
+			.tracked_offsets
+			.partition_point(|tracked| *tracked < range.start());
+		match self.tracked_offsets.get(index) {
+			Some(tracked) if *tracked <= range.end() => source_position(offset),
+			_ => empty_element(),
+		}
 	}
 
-	/// Formats the passed in token.
+	/// Formats the passed in token, with the comments of its leading and trailing trivia.
 	///
 	/// May return `None` if the token wasn't present in the original source but was inserted
 	/// by the parser to "fix" a syntax error and generate a valid tree.
 	///
+	/// The comments that precede the token on their own lines are printed on their own lines
+	/// before it, indented like the content of a block when the token closes one (`}`, `)` or `]`).
+	/// The comments that follow the token on the same line stay on that line: a `// comment` is
+	/// printed at the end of the line and forces a line break after it.
+	///
 	/// # Examples
 	///
 	/// ```
@@ -100,12 +231,115 @@ impl Formatter {
 	/// assert_eq!(Ok(token("'abc'")), result)
 	/// ```
 	pub fn format_token(&self, syntax_token: &SyntaxToken) -> FormatResult<FormatElement> {
-		Ok(token(syntax_token.text_trimmed()))
+		Ok(self.format_replaced(syntax_token, token(syntax_token.text_trimmed())))
+	}
+
+	/// Formats the comments of the token around the element that replaces its text, for the
+	/// tokens that are printed differently than in the source, or not at all, so that their
+	/// comments are kept
+	pub fn format_replaced(
+		&self,
+		current_token: &SyntaxToken,
+		content: FormatElement,
+	) -> FormatElement {
+		format_elements![
+			self.format_leading_comments(current_token),
+			// the start of the token is after its comments, unlike the start of its node
+			self.format_source_position(
+				current_token.text_range(),
+				current_token.text_trimmed_range().start()
+			),
+			content,
+			self.format_trailing_comments(current_token)
+		]
+	}
+
+	/// Formats the token if it's present in the source, else prints the text that the formatter
+	/// always inserts at its place, like a `;` at the end of a statement
+	pub fn format_or_insert_token(
+		&self,
+		syntax_token: Option<SyntaxToken>,
+		text: &str,
+	) -> FormatResult<FormatElement> {
+		match syntax_token {
+			Some(syntax_token) => Ok(self.format_replaced(&syntax_token, token(text))),
+			None => Ok(token(text)),
+		}
+	}
+
+	/// Formats the comments of the leading trivia of the token, each on its own line
+	fn format_leading_comments(&self, syntax_token: &SyntaxToken) -> FormatElement {
+		let pieces: Vec<_> = syntax_token.leading_trivia().pieces().collect();
+		let mut comments = Vec::new();
+
+		for (index, piece) in pieces.iter().enumerate() {
+			if let Some(comment) = piece.as_comments() {
+				let text = comment.text();
+				// a block comment followed by the token on the same line stays on that line
+				let separator = if is_line_comment(text) || has_line_break(&pieces[index + 1..]) {
+					hard_line_break()
+				} else {
+					space_token()
+				};
+				comments.push((format_comment(text), separator));
+			}
+		}
+
+		let (last, last_separator) = match comments.pop() {
+			Some(last) => last,
+			None => return FormatElement::Empty,
+		};
+		let comments = format_elements![
+			concat_elements(comments.into_iter().map(|(comment, separator)| {
+				format_elements![hard_line_break(), comment, separator]
+			})),
+			hard_line_break(),
+			last
+		];
+
+		// the comments before a closing token are the last ones of the content that it closes
+		if is_closing_token(syntax_token) {
+			format_elements![indent(comments), last_separator]
+		} else {
+			format_elements![comments, last_separator]
+		}
+	}
+
+	/// Formats the comments of the trailing trivia of the token, which are on the same line
+	fn format_trailing_comments(&self, syntax_token: &SyntaxToken) -> FormatElement {
+		let pieces: Vec<_> = syntax_token.trailing_trivia().pieces().collect();
+
+		concat_elements(pieces.iter().enumerate().filter_map(|(index, piece)| {
+			let comment = piece.as_comments()?;
+			let text = comment.text();
+
+			if is_line_comment(text) {
+				return Some(line_suffix(format_elements![space_token(), token(text)]));
+			}
+
+			// keeps the spaces around a block comment, `a /* b */, c` or `f(/* a */ b)`
+			let space_before = index > 0 && pieces[index - 1].as_whitespace().is_some();
+			let space_after =
+				matches!(pieces.get(index + 1), Some(piece) if piece.as_whitespace().is_some());
+			Some(format_elements![
+				if space_before {
+					space_token()
+				} else {
+					FormatElement::Empty
+				},
+				format_comment(text),
+				if space_after {
+					space_token()
+				} else {
+					FormatElement::Empty
+				}
+			])
+		}))
 	}
 
 	/// Formats each child and returns the result as a list.
 	///
-	/// Returns [None] if a child couldn't be formatted.
+	/// Returns the error of the first child that couldn't be formatted, see [Formatter::format_node].
 	pub fn format_nodes<T: AstNode + ToFormatElement>(
 		&self,
 		nodes: impl IntoIterator<Item = T>,
@@ -135,7 +369,12 @@ impl Formatter {
 			if let Some(separator) = element.trailing_separator()? {
 				let formatted_separator = self.format_token(&separator)?;
 				if index == list.len() - 1 {
-					result.push(format_elements![node, if_group_breaks(formatted_separator)])
+					// the comments of the trailing separator are kept even if it's omitted
+					let formatted_separator = self.format_replaced(
+						&separator,
+						if_group_breaks(token(separator.text_trimmed())),
+					);
+					result.push(format_elements![node, formatted_separator])
 				} else {
 					result.push(format_elements![node, formatted_separator]);
 				}
@@ -154,14 +393,534 @@ impl Formatter {
 	///
 	/// You may be inclined to call `node.text` directly. However, using `text` doesn't track the nodes
 	///nor its children source mapping information, resulting in incorrect source maps for this subtree.
+	///
+	/// The lines of the node are re-indented: their common indentation in the source is replaced by
+	/// the indentation of the enclosing code.
 	pub fn format_raw(&self, node: &SyntaxNode) -> FormatElement {
-		concat_elements(node.children_with_tokens().map(|child| match child {
-			SyntaxElement::Node(child_node) => {
-				// TODO: Add source map markers before/after node as well as any additional elements that
-				// need to be tracked for every node.
-				self.format_raw(&child_node)
+		format_source(node, true)
+	}
+
+	/// Formats a node that the formatter doesn't support as it is in the source, like
+	/// [Formatter::format_raw], except for the comments before and after it which are formatted
+	/// like the ones of the other tokens.
+	pub fn format_verbatim(&self, node: &SyntaxNode) -> FormatElement {
+		match (node.first_token(), node.last_token()) {
+			(Some(first_token), Some(last_token)) => format_elements![
+				self.format_leading_comments(&first_token),
+				self.format_source_position(node.text_range(), node.text_trimmed_range().start()),
+				format_source(node, false),
+				self.format_trailing_comments(&last_token)
+			],
+			_ => empty_element(),
+		}
+	}
+}
+
+/// Returns `true` if the JavaScript node contains TypeScript syntax that the formatting of the
+/// node would drop, like a type annotation or an accessibility modifier. The TypeScript syntax
+/// isn't formatted yet, such a node is printed verbatim.
+fn has_typescript_children(node: &SyntaxNode) -> bool {
+	!node.kind().is_ts()
+		&& node.children_with_tokens().any(|child| {
+			let kind = child.kind();
+			// these keywords are identifiers in JavaScript
+			kind.is_ts()
+				|| matches!(
+					kind,
+					T![private]
+						| T![protected] | T![public]
+						| T![readonly] | T![abstract]
+						| T![declare]
+				)
+		})
+}
+
+fn is_line_comment(text: &str) -> bool {
+	text.starts_with("//")
+}
+
+/// Returns `true` if the whitespace of the trivia contains a line break
+fn has_line_break(pieces: &[SyntaxTriviaPiece<JsLanguage>]) -> bool {
+	pieces
+		.iter()
+		.filter_map(SyntaxTriviaPiece::as_whitespace)
+		.any(|whitespace| whitespace.text().contains(&['\n', '\r'][..]))
+}
+
+/// Returns `true` if the token closes a block or a list, whose content it follows
+fn is_closing_token(syntax_token: &SyntaxToken) -> bool {
+	matches!(syntax_token.kind(), T!['}'] | T![')'] | T![']'])
+}
+
+/// A part of the source text of a node
+enum SourceText {
+	Token(String),
+	Trivia(String),
+}
+
+/// Formats the tokens and the trivia of the node as they are in the source, with the trivia
+/// before its first token and after its last token if `outer_trivia` is `true`.
+///
+/// The line breaks of the trivia become hard line breaks, so that the lines are indented like the
+/// enclosing code plus their indentation relative to the least indented line of the node. The
+/// relative indentation is re-indented with the indentation of the options: each indentation
+/// unit of the source is an indentation level, the rest is kept as spaces to preserve the
+/// alignment of lines like the ones of a block comment. The empty lines are kept, but not more
+/// than one in a row.
+fn format_source(node: &SyntaxNode, outer_trivia: bool) -> FormatElement {
+	let first_token = node.first_token();
+	let last_token = node.last_token();
+
+	let mut texts = Vec::new();
+	// the start of the line of the first token is a line of the node too
+	let mut first_line_indentation = None;
+	for syntax_token in node.descendants_tokens() {
+		let leading_trivia = normalize_line_breaks(syntax_token.leading_trivia().text());
+		if outer_trivia || Some(&syntax_token) != first_token.as_ref() {
+			push_trivia(&mut texts, leading_trivia);
+		} else if let Some((_, indentation)) = leading_trivia.rsplit_once('\n') {
+			first_line_indentation = Some(indentation_width(indentation));
+		}
+
+		texts.push(SourceText::Token(syntax_token.text_trimmed().to_string()));
+
+		if outer_trivia || Some(&syntax_token) != last_token.as_ref() {
+			let trailing_trivia = normalize_line_breaks(syntax_token.trailing_trivia().text());
+			push_trivia(&mut texts, trailing_trivia);
+		}
+	}
+
+	// the indentation of the lines that start with a token or a comment, and of the other lines,
+	// like the ones inside a block comment. The blank lines and the whitespace at the end of the
+	// node don't count.
+	let mut start_indentations: Vec<_> = first_line_indentation.into_iter().collect();
+	let mut other_indentations = Vec::new();
+	for (index, text) in texts.iter().enumerate() {
+		if let SourceText::Trivia(trivia) = text {
+			let is_last = index == texts.len() - 1;
+			let lines: Vec<_> = trivia.split('\n').skip(1).collect();
+			for (line_index, line) in lines.iter().enumerate() {
+				let content = line.trim_start();
+				if (line_index == lines.len() - 1 && !is_last)
+					|| content.starts_with("//")
+					|| content.starts_with("/*")
+				{
+					start_indentations.push(indentation_width(line));
+				} else if !content.is_empty() {
+					other_indentations.push(indentation_width(line));
+				}
 			}
-			SyntaxElement::Token(syntax_token) => token(syntax_token.text()),
-		}))
+		}
+	}
+
+	let indentation = start_indentations
+		.iter()
+		.chain(&other_indentations)
+		.copied()
+		.min()
+		.unwrap_or_default();
+	// the smallest indentation of a line relative to the node is the indentation unit of the source
+	let indentation_unit = start_indentations
+		.iter()
+		.map(|width| width - indentation)
+		.filter(|width| *width > 0)
+		.min()
+		.unwrap_or(1);
+
+	let mut elements = Vec::new();
+	for text in texts {
+		match text {
+			SourceText::Token(text) => elements.push(token(text)),
+			SourceText::Trivia(trivia) => {
+				let mut lines = trivia.split('\n');
+				let first_line = lines.next().unwrap_or_default();
+				let other_lines: Vec<_> = lines.collect();
+
+				let first_line = if other_lines.is_empty() {
+					first_line
+				} else {
+					first_line.trim_end()
+				};
+				if !first_line.is_empty() {
+					elements.push(token(first_line));
+				}
+
+				let mut has_empty_line = false;
+				for (line_index, line) in other_lines.iter().enumerate() {
+					let is_last_line = line_index == other_lines.len() - 1;
+					if !is_last_line && line.trim().is_empty() {
+						has_empty_line = true;
+						continue;
+					}
+
+					let line_break = if has_empty_line {
+						has_empty_line = false;
+						empty_line()
+					} else {
+						hard_line_break()
+					};
+
+					let width = indentation_width(line).saturating_sub(indentation);
+					let levels = width / indentation_unit;
+					elements.push((0..levels).fold(line_break, |element, _| indent(element)));
+
+					let content = line.trim_start();
+					let content = if is_last_line {
+						content
+					} else {
+						content.trim_end()
+					};
+					let alignment = width % indentation_unit;
+					if alignment > 0 || !content.is_empty() {
+						elements.push(token(format!("{}{}", " ".repeat(alignment), content)));
+					}
+				}
+			}
+		}
+	}
+
+	concat_elements(elements)
+}
+
+/// Adds the trivia to the source text, after the trivia that precedes it if any
+fn push_trivia(texts: &mut Vec<SourceText>, trivia: String) {
+	if trivia.is_empty() {
+		return;
+	}
+
+	match texts.last_mut() {
+		Some(SourceText::Trivia(previous)) => previous.push_str(&trivia),
+		_ => texts.push(SourceText::Trivia(trivia)),
+	}
+}
+
+fn normalize_line_breaks(text: &str) -> String {
+	text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// Returns the number of whitespace characters at the start of the line
+fn indentation_width(line: &str) -> usize {
+	line.chars()
+		.take_while(|char| *char == ' ' || *char == '\t')
+		.count()
+}
+
+/// Formats the text of a comment.
+///
+/// The lines of a block comment whose lines all start with a `*`, like a JSDoc comment, are
+/// indented like the code that follows the comment, with a space before the `*`. The other
+/// block comments are printed as they are.
+fn format_comment(text: &str) -> FormatElement {
+	let mut lines = text.lines();
+	let first_line = lines.next().unwrap_or_default();
+	let other_lines: Vec<_> = lines.map(str::trim).collect();
+
+	if other_lines.is_empty() {
+		token(text)
+	} else if other_lines.iter().all(|line| line.starts_with('*')) {
+		format_elements![
+			token(first_line.trim_end()),
+			hard_line_break(),
+			join_elements(
+				hard_line_break(),
+				other_lines
+					.into_iter()
+					.map(|line| token(format!(" {}", line)))
+			)
+		]
+	} else {
+		token(text.replace("\r\n", "\n").replace('\r', "\n"))
+	}
+}
+
+/// Returns the line ending of the first line of the tree, like [LineEndingStyle::detect]
+fn detect_line_ending(root: &SyntaxNode) -> LineEndingStyle {
+	let text = root.text();
+	match text.find_char('\n') {
+		Some(offset) => {
+			let previous = offset
+				.checked_sub(TextSize::from(1))
+				.and_then(|previous| text.char_at(previous));
+			if previous == Some('\r') {
+				LineEndingStyle::Crlf
+			} else {
+				LineEndingStyle::Lf
+			}
+		}
+		None if text.contains_char('\r') => LineEndingStyle::Cr,
+		None => LineEndingStyle::Lf,
+	}
+}
+
+/// Returns the position in the printed code of each tracked offset: it keeps its distance to the
+/// closest marker before it, without going past the closest marker after it. Of the markers of the
+/// same source offset, like the end of a node and the start of the next one, the first printed
+/// one is the closest.
+///
+/// The offsets must be sorted, the markers are sorted by their source offset and then walked
+/// along with the offsets.
+fn resolve_tracked_offsets(
+	offsets: &[TextSize],
+	markers: &[SourceMarker],
+	code_len: TextSize,
+) -> Vec<SourceMarker> {
+	let mut markers = markers.to_vec();
+	markers.sort_by_key(|marker| (marker.source, marker.dest));
+
+	// the index of the first marker that isn't before the current offset
+	let mut next = 0;
+	// the first printed marker of the closest source offset before the current offset
+	let mut previous: Option<&SourceMarker> = None;
+
+	offsets
+		.iter()
+		.map(|&source| {
+			while let Some(marker) = markers.get(next).filter(|marker| marker.source < source) {
+				if previous.map(|previous| previous.source) != Some(marker.source) {
+					previous = Some(marker);
+				}
+				next += 1;
+			}
+
+			let after = markers.get(next);
+			let before = match after {
+				Some(after) if after.source == source => Some(after),
+				_ => previous,
+			};
+
+			let mut dest = before.map_or(TextSize::from(0), |before| {
+				before.dest + (source - before.source)
+			});
+			if let Some(after) = after {
+				dest = dest.min(after.dest);
+			}
+
+			SourceMarker {
+				source,
+				dest: dest.min(code_len),
+			}
+		})
+		.collect()
+}
+
+/// Returns the smallest statement or module item that contains the range or, if the range spans
+/// several items of a list, the items of the list that intersect with it
+fn covering_items(root: &SyntaxNode, range: TextRange) -> Option<Vec<JsAnyModuleItem>> {
+	let covering_node = match root.covering_element(range) {
+		SyntaxElement::Node(node) => node,
+		SyntaxElement::Token(token) => token.parent()?,
+	};
+
+	for node in covering_node.ancestors() {
+		if let Some(item) = JsAnyModuleItem::cast(node.clone()) {
+			return Some(vec![item]);
+		}
+
+		let children: Vec<_> = node.children().collect();
+		let is_list_of_items = !children.is_empty()
+			&& children
+				.iter()
+				.all(|child| JsAnyModuleItem::can_cast(child.kind()));
+		if is_list_of_items {
+			let items: Vec<_> = children
+				.into_iter()
+				.filter(|child| {
+					matches!(
+						child.text_trimmed_range().intersect(range),
+						Some(intersection) if range.is_empty() || !intersection.is_empty()
+					)
+				})
+				.filter_map(JsAnyModuleItem::cast)
+				.collect();
+			if !items.is_empty() {
+				return Some(items);
+			}
+		}
+	}
+
+	None
+}
+
+/// Returns the range from the first comment to the last comment of the trivia, if it has any
+fn comments_range(trivia: &SyntaxTrivia<JsLanguage>) -> Option<TextRange> {
+	let mut comments = trivia
+		.pieces()
+		.filter(|piece| piece.as_comments().is_some())
+		.map(|piece| piece.text_range());
+	let first = comments.next()?;
+	let last = comments.last().unwrap_or(first);
+	Some(first.cover(last))
+}
+
+/// Returns the number of indentation levels of the line that contains the offset, according to
+/// the width of its whitespace at the start of the line
+fn indentation_level(root: &SyntaxNode, offset: TextSize, options: &PrinterOptions) -> usize {
+	// the text of the line before the offset, read from the tokens before it until a line break
+	let mut line = String::new();
+	let mut token = root.token_at_offset(offset).left_biased();
+	while let Some(current) = token {
+		let end = offset.min(current.text_range().end()) - current.text_range().start();
+		let text = &current.text()[..usize::from(end)];
+		match text.rfind(&['\n', '\r'][..]) {
+			Some(index) => {
+				line.insert_str(0, &text[index + 1..]);
+				break;
+			}
+			None => line.insert_str(0, text),
+		}
+		token = current.prev_token();
+	}
+
+	let tab_width = options.tab_width as usize;
+	let width_of = |text: &str| -> usize {
+		text.chars()
+			.map(|char| if char == '\t' { tab_width } else { 1 })
+			.sum()
+	};
+	let indentation: String = line
+		.chars()
+		.take_while(|char| *char == ' ' || *char == '\t')
+		.collect();
+
+	width_of(&indentation) / width_of(&options.indent_string).max(1)
+}
+
+#[cfg(test)]
+mod test {
+	use crate::{FormatOptions, SourceMarker};
+	use rslint_parser::{parse_module, TextRange, TextSize};
+
+	/// Formats the range between the two `|` markers of the source and returns the edited source
+	fn format_selection(source: &str) -> String {
+		let start = source.find('|').unwrap();
+		let end = source.rfind('|').unwrap() - 1;
+		let mut text = source.replacen('|', "", 2);
+		let range = TextRange::new(TextSize::from(start as u32), TextSize::from(end as u32));
+
+		let parse = parse_module(&text, 0);
+		let edit = super::Formatter::new(FormatOptions::default())
+			.format_range(&parse.syntax(), range)
+			.unwrap();
+		edit.apply(&mut text);
+		text
+	}
+
+	#[test]
+	fn formats_the_statement_at_its_indentation_level() {
+		assert_eq!(
+			format_selection("function f() {\n\tlet  a =  1;\n\tlet |b =  2|;\n}\nlet   c = 3;\n"),
+			"function f() {\n\tlet  a =  1;\n\tlet b = 2;\n}\nlet   c = 3;\n"
+		);
+	}
+
+	#[test]
+	fn formats_the_statements_that_intersect_with_the_range() {
+		assert_eq!(
+			format_selection("let  a =  |1;\nlet  b =  2;\nlet  c|  = 3;\nlet  d = 4;\n"),
+			"let a = 1;\nlet b = 2;\nlet c = 3;\nlet  d = 4;\n"
+		);
+	}
+
+	#[test]
+	fn formats_the_comments_of_the_statements() {
+		assert_eq!(
+			format_selection("let  a =  1;\n// leading\nlet  |b|  = 2; // trailing\n"),
+			"let  a =  1;\n// leading\nlet b = 2; // trailing\n"
+		);
+	}
+
+	/// Formats the source and returns the formatted code with the `|` cursors of the source at the
+	/// positions where they ended up
+	fn format_with_cursors(source: &str) -> String {
+		let offsets: Vec<_> = source
+			.match_indices('|')
+			.enumerate()
+			.map(|(index, (offset, _))| TextSize::from((offset - index) as u32))
+			.collect();
+		let text = source.replace('|', "");
+
+		let parse = parse_module(&text, 0);
+		let options = FormatOptions {
+			tracked_offsets: offsets.clone(),
+			..FormatOptions::default()
+		};
+		let formatted = super::Formatter::new(options)
+			.format_root(&parse.syntax())
+			.unwrap();
+
+		assert_eq!(
+			formatted
+				.sourcemap()
+				.iter()
+				.map(|marker| marker.source)
+				.collect::<Vec<_>>(),
+			offsets
+		);
+		let mut code = formatted.code().clone();
+		for marker in formatted.sourcemap().iter().rev() {
+			code.insert(usize::from(marker.dest), '|');
+		}
+		code
+	}
+
+	#[test]
+	fn tracks_the_offsets_through_the_formatting() {
+		assert_eq!(
+			format_with_cursors("let  a=|b;\nfunction  f(){ret|urn   a|}\n"),
+			"let a = |b;\nfunction f() {\n\tret|urn a|;\n}\n"
+		);
+	}
+
+	#[test]
+	fn tracks_the_offsets_after_the_comments() {
+		assert_eq!(
+			format_with_cursors("let a = 1;\n  // comment\n  |f|oo(  );|\n"),
+			"let a = 1;\n// comment\n|f|oo();|\n"
+		);
+	}
+
+	#[test]
+	fn resolves_the_offsets_from_the_closest_markers() {
+		let marker = |source: u32, dest: u32| SourceMarker {
+			source: TextSize::from(source),
+			dest: TextSize::from(dest),
+		};
+		// printed in another order than the source, and two markers for the offset 10
+		let markers = [marker(20, 4), marker(10, 12), marker(10, 9), marker(0, 0)];
+		let offsets: Vec<_> = [0, 5, 10, 12, 30]
+			.iter()
+			.map(|offset| TextSize::from(*offset))
+			.collect();
+
+		assert_eq!(
+			super::resolve_tracked_offsets(&offsets, &markers, TextSize::from(15)),
+			[
+				marker(0, 0),
+				marker(5, 5),
+				marker(10, 9),
+				marker(12, 4),
+				marker(30, 14)
+			]
+		);
+	}
+
+	#[test]
+	fn tracks_the_offsets_in_the_whitespace() {
+		assert_eq!(
+			format_with_cursors("|  let a = 1;  | \n\n\n|let b = 2;\n|"),
+			"|let a = 1;|\n|let b = 2;|\n"
+		);
+	}
+
+	#[test]
+	fn does_not_edit_the_code_outside_statements() {
+		assert_eq!(
+			format_selection("let  a =  1;\nlet  b = 2;\n||"),
+			"let  a =  1;\nlet  b = 2;\n"
+		);
+		assert_eq!(
+			format_selection("let  a =  1;\n// |end of| file\n"),
+			"let  a =  1;\n// end of file\n"
+		);
 	}
 }
@@ -62,18 +62,18 @@ pub use formatter::Formatter;
 use rslint_parser::SyntaxError;
 
 pub use format_element::{
-	block_indent, concat_elements, empty_element, group_elements, hard_line_break, if_group_breaks,
-	if_group_fits_on_single_line, indent, join_elements, soft_indent, soft_line_break,
-	soft_line_break_or_space, space_token, token, FormatElement,
+	block_indent, concat_elements, empty_element, empty_line, group_elements, hard_line_break,
+	if_group_breaks, if_group_fits_on_single_line, indent, join_elements, line_suffix, soft_indent,
+	soft_line_break, soft_line_break_or_space, source_position, space_token, token, FormatElement,
 };
 pub use printer::Printer;
-pub use printer::PrinterOptions;
-use rome_core::file_handlers::Language;
-use rome_core::App;
-use rome_path::RomePath;
-use rslint_parser::parse_text;
+pub use printer::{LineEnding, PrinterOptions};
+use rslint_errors::Severity;
+use rslint_parser::{Parse, ParserError, SyntaxNode, TextRange, TextSize};
+use rslint_text_edit::TextEdit;
 
-use std::io::Read;
+use std::fmt;
+use std::io;
 use std::str::FromStr;
 
 /// This trait should be implemented on each node/value that should have a formatted representation
@@ -95,6 +95,75 @@ pub enum FormatError {
 
 	/// When the ability to format the current file has been turned off on purpose
 	CapabilityDisabled,
+
+	/// The file to format couldn't be read or written
+	Io(FileError),
+
+	/// The source contains syntax errors, and the options don't allow to format it
+	SyntaxErrors(Vec<ParserError>),
+}
+
+/// The reasons why a file couldn't be read or written
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum FileError {
+	/// The file doesn't exist
+	NotFound,
+
+	/// The permissions of the file don't allow to read or write it
+	PermissionDenied,
+
+	/// The content of the file isn't valid UTF-8
+	InvalidUtf8,
+
+	/// Any other error, with its message
+	Other(String),
+}
+
+impl fmt::Display for FileError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			FileError::NotFound => write!(f, "the file doesn't exist"),
+			FileError::PermissionDenied => write!(f, "the permission to access the file is denied"),
+			FileError::InvalidUtf8 => write!(f, "the content of the file isn't valid UTF-8"),
+			FileError::Other(message) => write!(f, "{}", message),
+		}
+	}
+}
+
+impl From<io::Error> for FileError {
+	fn from(error: io::Error) -> Self {
+		match error.kind() {
+			io::ErrorKind::NotFound => FileError::NotFound,
+			io::ErrorKind::PermissionDenied => FileError::PermissionDenied,
+			// returned by `read_to_string` when the content isn't UTF-8
+			io::ErrorKind::InvalidData => FileError::InvalidUtf8,
+			_ => FileError::Other(error.to_string()),
+		}
+	}
+}
+
+impl From<io::Error> for FormatError {
+	fn from(error: io::Error) -> Self {
+		FormatError::Io(error.into())
+	}
+}
+
+impl fmt::Display for FormatError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			FormatError::MissingRequiredChild => {
+				write!(f, "a node required for the formatting is missing")
+			}
+			FormatError::UnsupportedLanguage => {
+				write!(f, "the language of the file is not supported")
+			}
+			FormatError::CapabilityDisabled => write!(f, "formatting is disabled for the file"),
+			FormatError::Io(error) => write!(f, "{}", error),
+			FormatError::SyntaxErrors(errors) => {
+				write!(f, "the file contains {} syntax error(s)", errors.len())
+			}
+		}
+	}
 }
 
 impl From<SyntaxError> for FormatError {
@@ -132,13 +201,70 @@ impl FromStr for IndentStyle {
 	}
 }
 
+/// The line ending of the formatted code
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum LineEndingStyle {
+	/// Line Feed only (\n)
+	Lf,
+	/// Carriage Return + Line Feed characters (\r\n)
+	Crlf,
+	/// Carriage Return character only (\r)
+	Cr,
+	/// The line ending of the first line of the formatted content, or \n if it has a single line
+	Auto,
+}
+
+impl LineEndingStyle {
+	/// Returns the line ending of the first line of the text, [LineEndingStyle::Lf] if
+	/// the text has a single line
+	pub fn detect(text: &str) -> Self {
+		match text.find('\n') {
+			Some(index) if text[..index].ends_with('\r') => Self::Crlf,
+			Some(_) => Self::Lf,
+			None if text.contains('\r') => Self::Cr,
+			None => Self::Lf,
+		}
+	}
+}
+
+impl Default for LineEndingStyle {
+	fn default() -> Self {
+		Self::Lf
+	}
+}
+
+impl FromStr for LineEndingStyle {
+	type Err = &'static str;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"lf" => Ok(Self::Lf),
+			"crlf" => Ok(Self::Crlf),
+			"cr" => Ok(Self::Cr),
+			"auto" => Ok(Self::Auto),
+			_ => Err("Value not supported for LineEndingStyle"),
+		}
+	}
+}
+
 #[derive(Debug)]
 pub struct FormatOptions {
 	/// The indent style
 	pub indent_style: IndentStyle,
 
+	/// The line ending, [LineEndingStyle::Auto] is resolved from the formatted content
+	pub line_ending: LineEndingStyle,
+
 	/// What's the max width of a line. Defaults to 80
 	pub line_width: u16,
+
+	/// Whether the sources with syntax errors are formatted, the nodes that contain the errors
+	/// being printed as they are. Defaults to `false`: the syntax errors are returned instead
+	pub format_with_errors: bool,
+
+	/// The source offsets, like the cursor positions of an editor, that the [Formatted::sourcemap]
+	/// of a formatted JavaScript source reports the position of in the formatted code
+	pub tracked_offsets: Vec<TextSize>,
 }
 
 impl FormatOptions {
@@ -155,73 +281,116 @@ impl Default for FormatOptions {
 		Self {
 			indent_style: IndentStyle::default(),
 			line_width: 80,
+			line_ending: LineEndingStyle::default(),
+			format_with_errors: false,
+			tracked_offsets: Vec::new(),
 		}
 	}
 }
 
+/// The position in the formatted code of an offset of the source
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SourceMarker {
+	/// The offset in the source
+	pub source: TextSize,
+	/// The offset in the formatted code
+	pub dest: TextSize,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Formatted {
 	code: String,
+	sourcemap: Vec<SourceMarker>,
 }
 
 impl Formatted {
 	pub fn new(code: &str) -> Self {
+		Self::with_sourcemap(code, Vec::new())
+	}
+
+	pub fn with_sourcemap(code: &str, sourcemap: Vec<SourceMarker>) -> Self {
 		Self {
 			code: String::from(code),
+			sourcemap,
 		}
 	}
 
 	pub fn code(&self) -> &String {
 		&self.code
 	}
-}
 
-// TODO: implement me + handle errors
-/// Main function
-pub fn format(rome_path: &mut RomePath, options: FormatOptions) -> FormatResult<Formatted> {
-	// we assume that file exists
-	let mut file = rome_path.open();
-	let mut buffer = String::new();
-	// we assume we have permissions
-	file.read_to_string(&mut buffer)
-		.expect("cannot read the file to format");
-
-	if let Some(handler) = rome_path.get_handler() {
-		if handler.capabilities().format {
-			let result = match handler.language() {
-				Language::Js => {
-					let parsed_result = parse_text(buffer.as_str(), 0);
-					Formatter::new(options).format_root(&parsed_result.syntax())
-				}
-				Language::Json => {
-					let element = tokenize_json(buffer.as_str());
-					Ok(format_element(&element, options))
-				}
-				Language::Ts | Language::Unknown => Err(FormatError::UnsupportedLanguage),
-			};
-
-			result
-		} else {
-			Err(FormatError::CapabilityDisabled)
-		}
-	} else {
-		Err(FormatError::UnsupportedLanguage)
+	/// Returns the positions of the source offsets in the formatted code: the ones tracked by the
+	/// [Formatter] ordered by source offset, see [FormatOptions::tracked_offsets], or the ones of
+	/// the printed [source_position] elements in the order they were printed.
+	pub fn sourcemap(&self) -> &[SourceMarker] {
+		&self.sourcemap
+	}
+
+	/// Returns the position in the formatted code of a source offset tracked by the [Formatter]
+	pub fn offset(&self, source: TextSize) -> Option<TextSize> {
+		self.sourcemap
+			.iter()
+			.find(|marker| marker.source == source)
+			.map(|marker| marker.dest)
 	}
 }
 
-pub fn format_file_and_save(rome_path: &mut RomePath, options: FormatOptions) {
-	let result = format(rome_path, options);
-	if let Ok(result) = result {
-		rome_path
-			.save(result.code())
-			.expect("Could not write the formatted code on file");
+/// Formats the syntax tree of a parsed JavaScript source.
+///
+/// The TypeScript syntax isn't formatted yet: the nodes that contain some, like a declaration with
+/// a type annotation or a class member with an accessibility modifier, are printed as they are in
+/// the source, and only the JavaScript code around them is formatted.
+///
+/// Returns [FormatError::SyntaxErrors] if the parser reported errors, unless
+/// [FormatOptions::format_with_errors] is set. The [Formatted::sourcemap] has the position of each
+/// of the [FormatOptions::tracked_offsets] in the formatted code.
+pub fn format<T>(parse: &Parse<T>, options: FormatOptions) -> FormatResult<Formatted> {
+	let errors: Vec<_> = parse
+		.errors()
+		.iter()
+		.filter(|error| matches!(error.severity, Severity::Error | Severity::Bug))
+		.cloned()
+		.collect();
+	if !errors.is_empty() && !options.format_with_errors {
+		return Err(FormatError::SyntaxErrors(errors));
 	}
+
+	let syntax_errors = errors
+		.iter()
+		.filter_map(|error| error.primary.as_ref())
+		.map(|primary| {
+			let range = &primary.span.range;
+			TextRange::new(
+				TextSize::from(range.start as u32),
+				TextSize::from(range.end as u32),
+			)
+		})
+		.collect();
+
+	Formatter::new(options)
+		.with_syntax_errors(syntax_errors)
+		.format_root(&parse.syntax())
 }
 
-pub fn format_file(path_to_file: &str, options: FormatOptions, app: &App) -> Formatted {
-	let mut rome_path = RomePath::new(path_to_file).deduce_handler(app);
-	let element = format(&mut rome_path, options);
-	element.unwrap()
+/// Formats the smallest statement of the syntax tree that contains the range, or the statements of a
+/// list that intersect with it, like the statements selected in an editor. Returns the edit that
+/// replaces them with their formatted code, which is empty if the range isn't inside a statement,
+/// see [Formatter::format_range].
+pub fn format_range(
+	root: &SyntaxNode,
+	range: TextRange,
+	options: FormatOptions,
+) -> FormatResult<TextEdit> {
+	Formatter::new(options).format_range(root, range)
+}
+
+/// Formats the content of a JSON file
+pub fn format_json(text: &str, mut options: FormatOptions) -> Formatted {
+	if options.line_ending == LineEndingStyle::Auto {
+		options.line_ending = LineEndingStyle::detect(text);
+	}
+	let element = tokenize_json(text);
+	format_element(&element, options)
 }
 
 pub fn format_element(element: &FormatElement, options: FormatOptions) -> Formatted {
@@ -1,5 +1,6 @@
-use crate::format_element::{ConditionalGroupContent, Group, GroupPrintMode, LineMode};
-use crate::{FormatElement, FormatOptions, Formatted, IndentStyle};
+use crate::format_element::{ConditionalGroupContent, Group, GroupPrintMode, LineMode, LineSuffix};
+use crate::{FormatElement, FormatOptions, Formatted, IndentStyle, LineEndingStyle, SourceMarker};
+use rslint_parser::TextSize;
 
 /// Options that affect how the [Printer] prints the format tokens
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -31,16 +32,22 @@ impl From<FormatOptions> for PrinterOptions {
 			IndentStyle::Space(width) => indent_string = " ".repeat(width as usize),
 		};
 
+		// `Auto` is resolved from the formatted content before creating the printer
+		let line_ending = match options.line_ending {
+			LineEndingStyle::Lf | LineEndingStyle::Auto => LineEnding::LineFeed,
+			LineEndingStyle::Crlf => LineEnding::CarriageReturnLineFeed,
+			LineEndingStyle::Cr => LineEnding::CarriageReturn,
+		};
+
 		PrinterOptions {
 			indent_string,
 			tab_width,
 			print_width: options.line_width,
-			..PrinterOptions::default()
+			line_ending,
 		}
 	}
 }
 
-#[allow(dead_code)]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum LineEnding {
 	///  Line Feed only (\n), common on Linux and macOS as well as inside git repos
@@ -96,15 +103,39 @@ impl Printer {
 
 	/// Prints the passed in element as well as all its content
 	pub fn print(mut self, element: &FormatElement) -> Formatted {
+		self.print_all(element, PrintElementArgs::default());
+		self.flush_source_positions();
+		self.flush_line_suffixes();
+
+		Formatted::with_sourcemap(self.state.buffer.as_str(), self.state.source_markers)
+	}
+
+	/// Prints the element and all its content
+	fn print_all(&mut self, element: &FormatElement, args: PrintElementArgs) {
 		let mut queue = ElementCallQueue::new();
 
-		queue.enqueue(PrintElementCall::new(element, PrintElementArgs::default()));
+		queue.enqueue(PrintElementCall::new(element, args));
 
 		while let Some(print_element_call) = queue.dequeue() {
 			queue.extend(self.print_element(print_element_call.element, print_element_call.args));
 		}
+	}
 
-		Formatted::new(self.state.buffer.as_str())
+	/// Prints the line suffixes queued since the last line break
+	fn flush_line_suffixes(&mut self) {
+		for (suffix, args) in std::mem::take(&mut self.state.line_suffixes) {
+			self.print_all(&suffix, args);
+		}
+	}
+
+	/// Sets the position of the pending source offsets to the current position in the buffer
+	fn flush_source_positions(&mut self) {
+		let dest = TextSize::of(self.state.buffer.as_str());
+		for source in std::mem::take(&mut self.state.pending_source_positions) {
+			self.state
+				.source_markers
+				.push(SourceMarker { source, dest });
+		}
 	}
 
 	/// Prints a single element and returns the elements to queue (that should be printed next).
@@ -115,7 +146,7 @@ impl Printer {
 	) -> Vec<PrintElementCall<'a>> {
 		match element {
 			FormatElement::Space => {
-				self.state.pending_spaces += 1;
+				self.state.pending_space = true;
 				vec![]
 			}
 			FormatElement::Empty => vec![],
@@ -131,12 +162,13 @@ impl Printer {
 					self.state.pending_indent = 0;
 				}
 
-				// Print pending spaces
-				if self.state.pending_spaces > 0 {
-					self.print_str(" ".repeat(self.state.pending_spaces as usize).as_str());
-					self.state.pending_spaces = 0;
+				// Print pending space
+				if self.state.pending_space {
+					self.print_str(" ");
+					self.state.pending_space = false;
 				}
 
+				self.flush_source_positions();
 				self.print_str(token);
 				vec![]
 			}
@@ -177,9 +209,35 @@ impl Printer {
 				vec![]
 			}
 
-			FormatElement::Line { .. } => {
-				self.print_str("\n");
-				self.state.pending_spaces = 0;
+			FormatElement::LineSuffix(LineSuffix { content }) => {
+				self.state
+					.line_suffixes
+					.push((content.as_ref().clone(), args));
+				vec![]
+			}
+
+			FormatElement::SourcePosition(source) => {
+				self.state.pending_source_positions.push(*source);
+				// the position is the one of the next token, after the pending space or indention
+				if !self.state.pending_space && self.state.pending_indent == 0 {
+					self.flush_source_positions();
+				}
+				vec![]
+			}
+
+			FormatElement::Line(line) => {
+				self.flush_source_positions();
+				self.flush_line_suffixes();
+
+				// consecutive line breaks, like the ones before and after a comment on its own
+				// line, are printed as a single one
+				if self.state.generated_column > 0 {
+					self.print_str("\n");
+				}
+				if line.mode == LineMode::Empty && !self.is_after_empty_line() {
+					self.print_str("\n");
+				}
+				self.state.pending_space = false;
 				self.state.pending_indent = args.indent;
 				vec![]
 			}
@@ -239,12 +297,12 @@ impl Printer {
 			FormatElement::Line(line) => {
 				match line.mode {
 					LineMode::SoftOrSpace => {
-						self.state.pending_spaces += 1;
+						self.state.pending_space = true;
 						vec![]
 					}
 					// We want a flat structure, so omit soft line wraps
 					LineMode::Soft => vec![],
-					LineMode::Hard => return Err(LineBreakRequiredError),
+					LineMode::Hard | LineMode::Empty => return Err(LineBreakRequiredError),
 				}
 			}
 
@@ -263,19 +321,40 @@ impl Printer {
 				..
 			}) => vec![],
 
+			// A line suffix, like a line comment, must be followed by a line break
+			FormatElement::LineSuffix(_) => return Err(LineBreakRequiredError),
+
 			FormatElement::Empty
 			| FormatElement::Space
 			| FormatElement::Indent { .. }
-			| FormatElement::List { .. } => self.print_element(element, args),
+			| FormatElement::List { .. }
+			| FormatElement::SourcePosition(_) => self.print_element(element, args),
 		};
 
 		Ok(next_calls)
 	}
 
+	/// Returns `true` if the last printed line is empty, or if nothing is printed yet
+	fn is_after_empty_line(&self) -> bool {
+		let line_ending = self.options.line_ending.as_str();
+		let buffer = self.state.buffer.as_str();
+		buffer.is_empty() || buffer == line_ending || buffer.ends_with(&line_ending.repeat(2))
+	}
+
 	fn print_str(&mut self, content: &str) {
 		self.state.buffer.reserve(content.len());
 
-		for char in content.chars() {
+		let mut chars = content.chars().peekable();
+		while let Some(char) = chars.next() {
+			// the line breaks of the source, like the ones of a template, use the line ending
+			// of the options too
+			let char = if char == '\r' {
+				chars.next_if_eq(&'\n');
+				'\n'
+			} else {
+				char
+			};
+
 			if char == '\n' {
 				for char in self.options.line_ending.as_str().chars() {
 					self.state.generated_index += 1;
@@ -309,53 +388,61 @@ impl Printer {
 struct PrinterState {
 	buffer: String,
 	pending_indent: u16,
-	pending_spaces: u16,
+	pending_space: bool,
 	generated_index: usize,
 	generated_line: usize,
 	generated_column: usize,
 	line_width: usize,
-	// mappings: Mapping[];
-	// We'll need to clone the line suffixes elements into the state.
-	// I guess that's fine. They're only used for comments and should, therefore, be very limited
-	// in size.
-	// lineSuffixes: [FormatElement, PrintElementArgs][];
+	/// The positions of the printed source offsets in the buffer
+	source_markers: Vec<SourceMarker>,
+	/// The source offsets whose position is the one of the next printed token
+	pending_source_positions: Vec<TextSize>,
+	/// The line suffixes to print before the next line break. They're cloned into the state,
+	/// which is fine since they're only used for comments and are, therefore, very limited in size.
+	line_suffixes: Vec<(FormatElement, PrintElementArgs)>,
 }
 
 impl PrinterState {
 	/// Allows creating a snapshot of the state that can be restored using [restore]
 	pub fn snapshot(&self) -> PrinterStateSnapshot {
 		PrinterStateSnapshot {
-			pending_spaces: self.pending_spaces,
+			pending_space: self.pending_space,
 			pending_indents: self.pending_indent,
 			generated_index: self.generated_index,
 			generated_line: self.generated_line,
 			generated_column: self.generated_column,
 			line_width: self.line_width,
 			buffer_position: self.buffer.len(),
+			source_markers_len: self.source_markers.len(),
+			pending_source_positions: self.pending_source_positions.clone(),
 		}
 	}
 
 	/// Restores the printer state to the state stored in the snapshot.
 	pub fn restore(&mut self, snapshot: PrinterStateSnapshot) {
-		self.pending_spaces = snapshot.pending_spaces;
+		self.pending_space = snapshot.pending_space;
 		self.pending_indent = snapshot.pending_indents;
 		self.generated_index = snapshot.generated_index;
 		self.generated_column = snapshot.generated_column;
 		self.generated_line = snapshot.generated_line;
 		self.line_width = snapshot.line_width;
 		self.buffer.truncate(snapshot.buffer_position);
+		self.source_markers.truncate(snapshot.source_markers_len);
+		self.pending_source_positions = snapshot.pending_source_positions;
 	}
 }
 
 /// Snapshot of a printer state.
 struct PrinterStateSnapshot {
 	pending_indents: u16,
-	pending_spaces: u16,
+	pending_space: bool,
 	generated_index: usize,
 	generated_column: usize,
 	generated_line: usize,
 	line_width: usize,
 	buffer_position: usize,
+	source_markers_len: usize,
+	pending_source_positions: Vec<TextSize>,
 }
 
 /// Stores arguments passed to `print_element` call, holding the state specific to printing an element.
@@ -439,9 +526,11 @@ mod tests {
 	use crate::format_element::join_elements;
 	use crate::printer::{LineEnding, Printer, PrinterOptions};
 	use crate::{
-		block_indent, format_elements, group_elements, hard_line_break, if_group_breaks,
-		soft_indent, soft_line_break, soft_line_break_or_space, token, FormatElement, Formatted,
+		block_indent, empty_line, format_elements, group_elements, hard_line_break,
+		if_group_breaks, line_suffix, soft_indent, soft_line_break, soft_line_break_or_space,
+		source_position, space_token, token, FormatElement, Formatted, SourceMarker,
 	};
+	use rslint_parser::TextSize;
 
 	/// Prints the given element with the default printer options
 	fn print_element<T: Into<FormatElement>>(element: T) -> Formatted {
@@ -531,6 +620,10 @@ two lines`,
 			"function main() {\r\n\tlet x = `This is a multiline\r\nstring`;\r\n}\r\n",
 			result.code()
 		);
+
+		let program = token("let x = `a\r\nb\rc`;");
+		let result = Printer::new(PrinterOptions::default()).print(&program);
+		assert_eq!("let x = `a\nb\nc`;", result.code());
 	}
 
 	#[test]
@@ -580,6 +673,82 @@ two lines`,
 		assert_eq!("[\n\t'a',\n\t\'b',\n\t\'c',\n\t'd',\n]", result.code());
 	}
 
+	#[test]
+	fn it_prints_the_line_suffixes_before_the_next_line_break() {
+		let result = print_element(format_elements![
+			create_array_element(vec![
+				format_elements![token("'a'"), line_suffix(token(" // a"))],
+				token("'b'"),
+			]),
+			token(";"),
+			line_suffix(token(" // end")),
+		]);
+
+		assert_eq!("[\n  'a', // a\n  'b',\n]; // end", result.code());
+	}
+
+	#[test]
+	fn it_prints_consecutive_line_breaks_as_one() {
+		let result = print_element(format_elements![
+			token("a"),
+			hard_line_break(),
+			hard_line_break(),
+			block_indent(format_elements![token("b"), hard_line_break()]),
+			token("c"),
+			empty_line(),
+			hard_line_break(),
+			token("d"),
+		]);
+
+		assert_eq!("a\n  b\nc\n\nd", result.code());
+
+		// nothing is printed before the line break, like in an empty file
+		let result = print_element(hard_line_break());
+		assert_eq!("", result.code());
+	}
+
+	#[test]
+	fn it_prints_consecutive_spaces_as_one() {
+		let result = print_element(format_elements![
+			token("a"),
+			space_token(),
+			space_token(),
+			token("b"),
+			space_token(),
+			hard_line_break(),
+			token("c"),
+		]);
+
+		assert_eq!("a b\nc", result.code());
+	}
+
+	#[test]
+	fn it_prints_the_source_positions_at_the_next_token() {
+		let result = print_element(create_array_element(vec![
+			format_elements![
+				source_position(TextSize::from(1)),
+				token("'a'"),
+				line_suffix(token(" // a"))
+			],
+			format_elements![token("'b'"), source_position(TextSize::from(12))],
+		]));
+
+		assert_eq!("[\n  'a', // a\n  'b',\n]", result.code());
+		assert_eq!(
+			&[
+				SourceMarker {
+					source: TextSize::from(1),
+					dest: TextSize::from(4),
+				},
+				SourceMarker {
+					source: TextSize::from(12),
+					dest: TextSize::from(19),
+				},
+			],
+			result.sourcemap()
+		);
+	}
+
 	fn create_array_element(items: Vec<FormatElement>) -> FormatElement {
 		let separator = format_elements![token(","), soft_line_break_or_space(),];
 
@@ -1,5 +1,6 @@
 use crate::{FormatElement, FormatResult, Formatter, ToFormatElement};
 use rslint_parser::ast::JsAnyAssignment;
+use rslint_parser::AstNode;
 
 impl ToFormatElement for JsAnyAssignment {
 	fn to_format_element(&self, formatter: &Formatter) -> FormatResult<FormatElement> {
@@ -7,10 +8,7 @@ impl ToFormatElement for JsAnyAssignment {
 			JsAnyAssignment::JsIdentifierAssignment(identifier) => {
 				identifier.to_format_element(formatter)
 			}
-			JsAnyAssignment::JsStaticMemberAssignment(_) => todo!(),
-			JsAnyAssignment::JsComputedMemberAssignment(_) => todo!(),
-			JsAnyAssignment::JsParenthesizedAssignment(_) => todo!(),
-			JsAnyAssignment::JsUnknownAssignment(_) => todo!(),
+			_ => self.syntax().to_format_element(formatter),
 		}
 	}
 }
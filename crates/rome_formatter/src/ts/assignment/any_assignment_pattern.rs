@@ -1,5 +1,6 @@
 use crate::{FormatElement, FormatResult, Formatter, ToFormatElement};
 use rslint_parser::ast::JsAnyAssignmentPattern;
+use rslint_parser::AstNode;
 
 impl ToFormatElement for JsAnyAssignmentPattern {
 	fn to_format_element(&self, formatter: &Formatter) -> FormatResult<FormatElement> {
@@ -7,8 +8,7 @@ impl ToFormatElement for JsAnyAssignmentPattern {
 			JsAnyAssignmentPattern::JsAnyAssignment(assignment) => {
 				assignment.to_format_element(formatter)
 			}
-			JsAnyAssignmentPattern::JsArrayAssignmentPattern(_) => todo!(),
-			JsAnyAssignmentPattern::JsObjectAssignmentPattern(_) => todo!(),
+			_ => self.syntax().to_format_element(formatter),
 		}
 	}
 }
@@ -3,6 +3,7 @@ use crate::{
 	Formatter, ToFormatElement,
 };
 use rslint_parser::ast::{JsAnyArrayBindingPatternElement, JsArrayBindingPattern};
+use rslint_parser::AstNode;
 
 impl ToFormatElement for JsArrayBindingPattern {
 	fn to_format_element(&self, formatter: &Formatter) -> FormatResult<FormatElement> {
@@ -28,7 +29,7 @@ impl ToFormatElement for JsAnyArrayBindingPatternElement {
 			JsAnyArrayBindingPatternElement::JsBindingPatternWithDefault(with_default) => {
 				with_default.to_format_element(formatter)
 			}
-			JsAnyArrayBindingPatternElement::JsArrayBindingPatternRestElement(_) => todo!(),
+			_ => self.syntax().to_format_element(formatter),
 		}
 	}
 }
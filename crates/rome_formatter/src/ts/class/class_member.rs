@@ -1,5 +1,6 @@
 use crate::{FormatElement, FormatResult, Formatter, ToFormatElement};
 use rslint_parser::ast::JsAnyClassMember;
+use rslint_parser::AstNode;
 
 impl ToFormatElement for JsAnyClassMember {
 	fn to_format_element(&self, formatter: &Formatter) -> FormatResult<FormatElement> {
@@ -16,8 +17,7 @@ impl ToFormatElement for JsAnyClassMember {
 			}
 			JsAnyClassMember::JsGetterClassMember(getter) => getter.to_format_element(formatter),
 			JsAnyClassMember::JsSetterClassMember(setter) => setter.to_format_element(formatter),
-			JsAnyClassMember::JsUnknownMember(_) => todo!(),
-			JsAnyClassMember::TsIndexSignature(_) => todo!(),
+			_ => self.syntax().to_format_element(formatter),
 		}
 	}
 }
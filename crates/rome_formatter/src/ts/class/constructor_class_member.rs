@@ -5,6 +5,7 @@ use crate::{
 use rslint_parser::ast::{
 	JsAnyConstructorParameter, JsConstructorClassMember, JsConstructorParameterList,
 };
+use rslint_parser::AstNode;
 
 impl ToFormatElement for JsConstructorClassMember {
 	fn to_format_element(&self, formatter: &Formatter) -> FormatResult<FormatElement> {
@@ -34,13 +35,13 @@ impl ToFormatElement for JsConstructorParameterList {
 impl ToFormatElement for JsAnyConstructorParameter {
 	fn to_format_element(&self, formatter: &Formatter) -> FormatResult<FormatElement> {
 		match self {
-			JsAnyConstructorParameter::TsConstructorParam(_) => todo!(),
 			JsAnyConstructorParameter::JsBindingPatternWithDefault(binding) => {
 				binding.to_format_element(formatter)
 			}
 			JsAnyConstructorParameter::JsAnyBindingPattern(binding) => {
 				binding.to_format_element(formatter)
 			}
+			_ => self.syntax().to_format_element(formatter),
 		}
 	}
 }
@@ -1,5 +1,5 @@
 use crate::{
-	empty_element, format_elements, space_token, token, FormatElement, FormatResult, Formatter,
+	empty_element, format_elements, space_token, FormatElement, FormatResult, Formatter,
 	ToFormatElement,
 };
 use rslint_parser::ast::JsPropertyClassMember;
@@ -22,7 +22,7 @@ impl ToFormatElement for JsPropertyClassMember {
 			static_token,
 			formatter.format_node(self.name()?)?,
 			init,
-			token(";")
+			formatter.format_or_insert_token(self.semicolon_token(), ";")?
 		])
 	}
 }
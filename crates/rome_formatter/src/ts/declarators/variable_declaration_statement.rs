@@ -1,5 +1,5 @@
 use crate::{
-	empty_element, format_elements, join_elements, space_token, token, FormatElement, FormatResult,
+	empty_element, format_elements, join_elements, space_token, FormatElement, FormatResult,
 	Formatter, ToFormatElement,
 };
 use rslint_parser::ast::{
@@ -10,7 +10,7 @@ impl ToFormatElement for JsVariableDeclarationStatement {
 	fn to_format_element(&self, formatter: &Formatter) -> FormatResult<FormatElement> {
 		Ok(format_elements![
 			formatter.format_node(self.declaration()?)?,
-			token(";"),
+			formatter.format_or_insert_token(self.semicolon_token(), ";")?,
 		])
 	}
 }
@@ -6,12 +6,21 @@ use rslint_parser::ast::{JsArrayExpression, JsArrayHole};
 
 impl ToFormatElement for JsArrayExpression {
 	fn to_format_element(&self, formatter: &Formatter) -> FormatResult<FormatElement> {
+		// a trailing separator of the source is kept by `format_separated`
+		let trailing_separator =
+			if self.elements().is_empty() || self.elements().trailing_separator().is_some() {
+				empty_element()
+			} else {
+				if_group_breaks(token(","))
+			};
 		let elements = formatter.format_separated(self.elements())?;
 
 		Ok(group_elements(format_elements!(
 			formatter.format_token(&self.l_brack_token()?)?,
-			soft_indent(join_elements(soft_line_break_or_space(), elements)),
-			if_group_breaks(token(",")),
+			soft_indent(format_elements![
+				join_elements(soft_line_break_or_space(), elements),
+				trailing_separator
+			]),
 			formatter.format_token(&self.r_brack_token()?)?,
 		)))
 	}
@@ -8,7 +8,7 @@ use rslint_parser::ast::{
 	JsParenthesizedExpression, JsThisExpression, JsUnaryExpression, JsYieldExpression, NewExpr,
 	NewTarget,
 };
-use rslint_parser::{token_set, TokenSet, T};
+use rslint_parser::{token_set, AstNode, TokenSet, T};
 
 impl ToFormatElement for JsAnyExpression {
 	fn to_format_element(&self, formatter: &Formatter) -> FormatResult<FormatElement> {
@@ -17,7 +17,6 @@ impl ToFormatElement for JsAnyExpression {
 			JsAnyExpression::JsAnyLiteralExpression(literal) => {
 				literal.to_format_element(formatter)
 			}
-			JsAnyExpression::Template(_) => todo!(),
 			JsAnyExpression::JsIdentifierExpression(identifier_expr) => {
 				identifier_expr.to_format_element(formatter)
 			}
@@ -36,7 +35,6 @@ impl ToFormatElement for JsAnyExpression {
 			JsAnyExpression::JsComputedMemberExpression(computed_member_expression) => {
 				computed_member_expression.to_format_element(formatter)
 			}
-			JsAnyExpression::JsStaticMemberExpression(_) => todo!(),
 			JsAnyExpression::NewExpr(new_expr) => new_expr.to_format_element(formatter),
 			JsAnyExpression::CallExpr(call_expression) => {
 				call_expression.to_format_element(formatter)
@@ -61,7 +59,6 @@ impl ToFormatElement for JsAnyExpression {
 				class_expression.to_format_element(formatter)
 			}
 			JsAnyExpression::NewTarget(expr) => expr.to_format_element(formatter),
-			JsAnyExpression::ImportMeta(_) => todo!(),
 			JsAnyExpression::JsImportCallExpression(import_call_expr) => {
 				import_call_expr.to_format_element(formatter)
 			}
@@ -71,20 +68,17 @@ impl ToFormatElement for JsAnyExpression {
 			JsAnyExpression::JsAwaitExpression(await_expression) => {
 				await_expression.to_format_element(formatter)
 			}
-			JsAnyExpression::TsNonNull(_) => todo!(),
-			JsAnyExpression::TsAssertion(_) => todo!(),
-			JsAnyExpression::TsConstAssertion(_) => todo!(),
 			JsAnyExpression::JsPreUpdateExpression(pre_update_expression) => {
 				pre_update_expression.to_format_element(formatter)
 			}
 			JsAnyExpression::JsPostUpdateExpression(post_update_expression) => {
 				post_update_expression.to_format_element(formatter)
 			}
-			JsAnyExpression::JsUnknownExpression(_) => todo!(),
 			JsAnyExpression::JsLogicalExpression(logical_expression) => {
 				logical_expression.to_format_element(formatter)
 			}
 			JsAnyExpression::JsSuperExpression(expr) => expr.to_format_element(formatter),
+			_ => self.syntax().to_format_element(formatter),
 		}
 	}
 }
@@ -1,17 +1,26 @@
 use crate::{
-	format_elements, group_elements, if_group_breaks, join_elements, soft_indent,
+	empty_element, format_elements, group_elements, if_group_breaks, join_elements, soft_indent,
 	soft_line_break_or_space, token, FormatElement, FormatResult, Formatter, ToFormatElement,
 };
 use rslint_parser::ast::JsObjectExpression;
 
 impl ToFormatElement for JsObjectExpression {
 	fn to_format_element(&self, formatter: &Formatter) -> FormatResult<FormatElement> {
+		// a trailing separator of the source is kept by `format_separated`
+		let trailing_separator =
+			if self.members().is_empty() || self.members().trailing_separator().is_some() {
+				empty_element()
+			} else {
+				if_group_breaks(token(","))
+			};
 		let props = formatter.format_separated(self.members())?;
 
 		Ok(group_elements(format_elements!(
 			formatter.format_token(&self.l_curly_token()?)?,
-			soft_indent(join_elements(soft_line_break_or_space(), props)),
-			if_group_breaks(token(",")),
+			soft_indent(format_elements![
+				join_elements(soft_line_break_or_space(), props),
+				trailing_separator
+			]),
 			formatter.format_token(&self.r_curly_token()?)?,
 		)))
 	}
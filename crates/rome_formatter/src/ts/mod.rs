@@ -8,6 +8,7 @@ mod declarators;
 mod expressions;
 mod ident;
 mod import;
+mod module;
 mod object_members;
 mod parameter_list;
 mod script;
@@ -0,0 +1,55 @@
+use crate::{
+	concat_elements, empty_element, hard_line_break, join_elements, FormatElement, FormatResult,
+	Formatter, ToFormatElement,
+};
+use rslint_parser::ast::{AstNodeList, JsAnyModuleItem, JsModule};
+use rslint_parser::{AstNode, SyntaxKind, SyntaxNode};
+
+impl ToFormatElement for JsModule {
+	fn to_format_element(&self, formatter: &Formatter) -> FormatResult<FormatElement> {
+		let mut elements = vec![];
+
+		if let Some(interpreter) = self.interpreter_token() {
+			elements.push(formatter.format_token(&interpreter)?);
+			elements.push(hard_line_break());
+		}
+
+		elements.push(format_module_items(self.items(), formatter)?);
+		elements.push(format_end_of_file(self.syntax(), formatter)?);
+
+		elements.push(hard_line_break());
+
+		Ok(concat_elements(elements))
+	}
+}
+
+impl ToFormatElement for JsAnyModuleItem {
+	fn to_format_element(&self, formatter: &Formatter) -> FormatResult<FormatElement> {
+		match self {
+			JsAnyModuleItem::JsAnyStatement(statement) => statement.to_format_element(formatter),
+			_ => self.syntax().to_format_element(formatter),
+		}
+	}
+}
+
+/// Formats the items of a module, one per line
+fn format_module_items(
+	items: AstNodeList<JsAnyModuleItem>,
+	formatter: &Formatter,
+) -> FormatResult<FormatElement> {
+	Ok(join_elements(
+		hard_line_break(),
+		formatter.format_nodes(items)?,
+	))
+}
+
+/// Formats the comments at the end of a script or a module, which precede the end of file token
+pub(crate) fn format_end_of_file(
+	root: &SyntaxNode,
+	formatter: &Formatter,
+) -> FormatResult<FormatElement> {
+	match root.last_token() {
+		Some(eof) if eof.kind() == SyntaxKind::EOF => formatter.format_token(&eof),
+		_ => Ok(empty_element()),
+	}
+}
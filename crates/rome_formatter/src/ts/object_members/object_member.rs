@@ -1,5 +1,6 @@
 use crate::{FormatElement, FormatResult, Formatter, ToFormatElement};
 use rslint_parser::ast::JsAnyObjectMember;
+use rslint_parser::AstNode;
 
 impl ToFormatElement for JsAnyObjectMember {
 	fn to_format_element(&self, formatter: &Formatter) -> FormatResult<FormatElement> {
@@ -13,8 +14,7 @@ impl ToFormatElement for JsAnyObjectMember {
 			JsAnyObjectMember::JsShorthandPropertyObjectMember(ident) => {
 				ident.to_format_element(formatter)
 			}
-			JsAnyObjectMember::JsMethodObjectMember(_) => todo!(),
-			JsAnyObjectMember::JsUnknownMember(_) => todo!(),
+			_ => self.syntax().to_format_element(formatter),
 		}
 	}
 }
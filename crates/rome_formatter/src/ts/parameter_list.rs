@@ -3,6 +3,7 @@ use crate::{
 	FormatElement, FormatResult, Formatter, ToFormatElement,
 };
 use rslint_parser::ast::{JsAnyParameter, JsParameterList};
+use rslint_parser::AstNode;
 
 impl ToFormatElement for JsParameterList {
 	fn to_format_element(&self, formatter: &Formatter) -> FormatResult<FormatElement> {
@@ -23,7 +24,7 @@ impl ToFormatElement for JsAnyParameter {
 			JsAnyParameter::JsBindingPatternWithDefault(binding) => {
 				binding.to_format_element(formatter)
 			}
-			JsAnyParameter::JsRestParameter(_) => todo!("rest parameter"),
+			_ => self.syntax().to_format_element(formatter),
 		}
 	}
 }
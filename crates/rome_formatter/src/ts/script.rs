@@ -1,8 +1,10 @@
+use crate::ts::module::format_end_of_file;
 use crate::ts::statements::format_statements;
 use crate::{
-	format_elements, hard_line_break, FormatElement, FormatResult, Formatter, ToFormatElement,
+	concat_elements, hard_line_break, FormatElement, FormatResult, Formatter, ToFormatElement,
 };
 use rslint_parser::ast::JsScript;
+use rslint_parser::AstNode;
 
 impl ToFormatElement for JsScript {
 	fn to_format_element(&self, formatter: &Formatter) -> FormatResult<FormatElement> {
@@ -14,10 +16,10 @@ impl ToFormatElement for JsScript {
 		}
 
 		elements.push(format_statements(self.statements(), formatter));
+		elements.push(format_end_of_file(self.syntax(), formatter)?);
 
-		Ok(format_elements![
-			concat_elements(elements),
-			hard_line_break()
-		])
+		elements.push(hard_line_break());
+
+		Ok(concat_elements(elements))
 	}
 }
@@ -1,5 +1,5 @@
 use crate::{
-	empty_element, format_elements, space_token, token, FormatElement, FormatResult, Formatter,
+	empty_element, format_elements, space_token, FormatElement, FormatResult, Formatter,
 	ToFormatElement,
 };
 use rslint_parser::ast::JsBreakStatement;
@@ -15,7 +15,7 @@ impl ToFormatElement for JsBreakStatement {
 		Ok(format_elements![
 			formatter.format_token(&self.break_token()?)?,
 			label,
-			token(";")
+			formatter.format_or_insert_token(self.semicolon_token(), ";")?
 		])
 	}
 }
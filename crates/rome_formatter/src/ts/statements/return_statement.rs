@@ -1,5 +1,5 @@
 use crate::{
-	concat_elements, space_token, token, FormatElement, FormatResult, Formatter, ToFormatElement,
+	concat_elements, space_token, FormatElement, FormatResult, Formatter, ToFormatElement,
 };
 use rslint_parser::ast::JsReturnStatement;
 
@@ -12,7 +12,7 @@ impl ToFormatElement for JsReturnStatement {
 			tokens.push(formatter.format_node(argument)?);
 		}
 
-		tokens.push(token(";"));
+		tokens.push(formatter.format_or_insert_token(self.semicolon_token(), ";")?);
 
 		Ok(concat_elements(tokens))
 	}
@@ -48,23 +48,15 @@ impl ToFormatElement for JsAnyStatement {
 				debugger_statement.to_format_element(formatter)
 			}
 
-			JsAnyStatement::ForOfStmt(_) => todo!(),
 			JsAnyStatement::JsFunctionDeclaration(decl) => decl.to_format_element(formatter),
 			JsAnyStatement::JsClassDeclaration(decl) => decl.to_format_element(formatter),
 			JsAnyStatement::JsVariableDeclarationStatement(decl) => {
 				decl.to_format_element(formatter)
 			}
-			JsAnyStatement::JsUnknownStatement(unknown_statement) => {
-				Ok(formatter.format_raw(unknown_statement.syntax()))
-			}
 			JsAnyStatement::JsTryFinallyStatement(try_finally) => {
 				try_finally.to_format_element(formatter)
 			}
-			JsAnyStatement::TsEnum(_) => todo!(),
-			JsAnyStatement::TsTypeAliasDecl(_) => todo!(),
-			JsAnyStatement::TsNamespaceDecl(_) => todo!(),
-			JsAnyStatement::TsModuleDecl(_) => todo!(),
-			JsAnyStatement::TsInterfaceDecl(_) => todo!(),
+			_ => self.syntax().to_format_element(formatter),
 		}
 	}
 }
@@ -1,5 +1,5 @@
 use crate::{
-	format_elements, space_token, token, FormatElement, FormatResult, Formatter, ToFormatElement,
+	format_elements, space_token, FormatElement, FormatResult, Formatter, ToFormatElement,
 };
 use rslint_parser::ast::JsThrowStatement;
 
@@ -11,7 +11,7 @@ impl ToFormatElement for JsThrowStatement {
 			throw_token,
 			space_token(),
 			exception,
-			token(";")
+			formatter.format_or_insert_token(self.semicolon_token(), ";")?
 		])
 	}
 }
@@ -1,5 +1,5 @@
 use rome_core::create_app;
-use rome_formatter::{format_file, FormatOptions};
+use rome_formatter::FormatOptions;
 use std::fs;
 use std::path::Path;
 
@@ -14,9 +14,11 @@ use std::path::Path;
 ///
 /// * `json/null` -> input: `tests/specs/json/null.json`, expected output: `tests/specs/json/null.expected.json`
 /// * `null` -> input: `tests/specs/null.json`, expected output: `tests/specs/null.expected.json`
+///
+/// The inputs of the `invalid` directories contain syntax errors, they're formatted with
+/// [FormatOptions::format_with_errors].
 pub fn run(spec_input_file: &str, expected_file: &str) {
 	let app = create_app();
-	let file_path = &spec_input_file;
 	let spec_input_file = Path::new(spec_input_file);
 	let expected_file = Path::new(expected_file);
 
@@ -32,7 +34,14 @@ pub fn run(spec_input_file: &str, expected_file: &str) {
 		expected_file.display(),
 	);
 
-	let result = format_file(file_path, FormatOptions::default(), &app);
+	let input = fs::read_to_string(spec_input_file).unwrap();
+	let options = FormatOptions {
+		format_with_errors: spec_input_file
+			.components()
+			.any(|component| component.as_os_str() == "invalid"),
+		..FormatOptions::default()
+	};
+	let result = app.format(spec_input_file, &input, options).unwrap();
 	let expected_output = fs::read_to_string(expected_file).unwrap();
 
 	assert_eq!(&expected_output, result.code());
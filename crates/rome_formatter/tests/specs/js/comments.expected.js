@@ -0,0 +1,27 @@
+// leading comment of the file
+/**
+ * Documentation
+ * of the function
+ */
+function foo(a /* inline */, b) {
+	// only comment of the block
+}
+let a = 1; // without semicolon
+a; // after a statement
+const obj = {
+	a: 1, // first
+	b: 2, // second
+};
+const arr = [
+	1,
+	2, // last
+];
+if (a) {
+	b();
+	// after the last statement
+}
+/* before */ call(x);
+function f() {
+	return; // nothing
+}
+// last comment of the file
@@ -1,4 +1,3 @@
-x = y!
 x = ++y
 x = --y
 x = y++
@@ -1,8 +1,8 @@
 function test() {
 	let x = 10;
 	if test {
-					return 10;
-			}
+				return 10;
+		}
 	let y = 100;
 }
 if (true) {
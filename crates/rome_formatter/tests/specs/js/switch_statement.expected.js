@@ -4,3 +4,6 @@ switch (key) {
 	default:
 		break;
 }
+switch (key) {
+	case empty:
+}
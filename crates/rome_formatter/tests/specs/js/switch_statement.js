@@ -8,3 +8,6 @@ switch (key) {
 
 
 }
+switch (key) {
+	case empty: ;
+}
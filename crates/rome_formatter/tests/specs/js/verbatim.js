@@ -0,0 +1,20 @@
+function foo(...rest) {
+    for (const item of items) {
+        console.log(item.name);
+
+        /**
+         * logs the item
+         */
+        log(`item:
+  ${item}`);
+    }
+}
+let { a, b } = c;
+let re = /ab+c/gi;
+a.b.c = 1;
+class A {
+  #secret = 1;
+  [computed] = 2;
+}
+let o = { method() { return 1; } };
+let meta = import.meta;
@@ -0,0 +1,11 @@
+let a: number =   1
+let   b = a as B;
+class A {
+	private x = 1;
+	readonly y: string;
+	m<T>(a): void {
+		return;
+	}
+}
+function f() {   return   g(c!); }
+type T = { a: 1 };
@@ -0,0 +1,481 @@
+//! Discovery of the files that a command should process.
+//!
+//! The inputs passed on the command line can be:
+//! - a path to a file, which is returned as is;
+//! - a path to a directory, which is walked recursively;
+//! - a glob pattern, like `tests/**/*.js`, which is matched against the files found
+//!   by walking the directory that precedes the first glob segment.
+//!
+//! The files found by walking a directory can be further filtered with include and ignore
+//! patterns, see [FileDiscovery].
+//!
+//! While walking, the files and directories listed in the [IGNORE_FILE] files are skipped,
+//! as well as the ones listed in the `.gitignore` files when [FileDiscovery::with_gitignore]
+//! is enabled. Ignore files follow the semantics of `.gitignore` files: patterns can be negated
+//! with `!`, can match only directories with a trailing `/`, and the ignore files inside
+//! a directory apply to its content only.
+use globset::{Glob, GlobBuilder, GlobMatcher, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use std::collections::HashSet;
+use std::env;
+use std::path::{Component, Path, PathBuf};
+
+/// The name of the files that list the paths that should be skipped while walking directories
+pub const IGNORE_FILE: &str = ".romeignore";
+
+/// The files found while traversing the inputs
+#[derive(Debug, Default)]
+pub struct DiscoveredFiles {
+	/// The files that matched the inputs, sorted by input and then by path
+	pub files: Vec<PathBuf>,
+
+	/// The inputs that don't exist on disk or that didn't match any file
+	pub unmatched: Vec<String>,
+}
+
+/// Resolves the given inputs to a list of files.
+///
+/// Each file is returned only once, even if it's matched by more than one input.
+///
+/// ```rust
+/// use rome_path::discover_files;
+///
+/// let discovered = discover_files(["src/lib.rs", "does/not/exist.js"]);
+/// assert_eq!(discovered.files.len(), 1);
+/// assert_eq!(discovered.unmatched, vec![String::from("does/not/exist.js")]);
+/// ```
+pub fn discover_files<I, S>(inputs: I) -> DiscoveredFiles
+where
+	I: IntoIterator<Item = S>,
+	S: AsRef<str>,
+{
+	FileDiscovery::default().discover(inputs)
+}
+
+/// Resolves inputs to a list of files, like [discover_files], keeping only the files
+/// found while walking directories that match the include patterns and don't match
+/// the ignore patterns.
+///
+/// The paths passed explicitly as inputs are always returned.
+///
+/// ```rust
+/// use rome_path::FileDiscovery;
+///
+/// let discovered = FileDiscovery::default()
+///     .with_ignore(["src/lib.rs"])
+///     .unwrap()
+///     .discover(["src", "src/lib.rs"]);
+/// assert!(!discovered.files[..discovered.files.len() - 1]
+///     .iter()
+///     .any(|file| file.to_str() == Some("src/lib.rs")));
+/// assert_eq!(discovered.files.last().unwrap().to_str(), Some("src/lib.rs"));
+/// ```
+#[derive(Debug, Default)]
+pub struct FileDiscovery {
+	/// The directory the patterns are relative to, the current directory if [None]
+	root: Option<PathBuf>,
+	include: Option<GlobSet>,
+	ignore: Option<GlobSet>,
+	/// Whether the `.gitignore` files are honored, in addition to the [IGNORE_FILE] files
+	gitignore: bool,
+}
+
+impl FileDiscovery {
+	/// Sets the directory that the include and ignore patterns are relative to
+	pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+		self.root = Some(root.into());
+		self
+	}
+
+	/// Also skips the files listed in the `.gitignore` files while walking directories
+	pub fn with_gitignore(mut self, gitignore: bool) -> Self {
+		self.gitignore = gitignore;
+		self
+	}
+
+	/// Only keeps the files that match at least one of the patterns.
+	///
+	/// Returns an error if one of the patterns isn't a valid glob.
+	pub fn with_include<I, S>(mut self, patterns: I) -> Result<Self, globset::Error>
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<str>,
+	{
+		self.include = build_glob_set(patterns)?;
+		Ok(self)
+	}
+
+	/// Removes the files that match any of the patterns.
+	///
+	/// Returns an error if one of the patterns isn't a valid glob.
+	pub fn with_ignore<I, S>(mut self, patterns: I) -> Result<Self, globset::Error>
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<str>,
+	{
+		self.ignore = build_glob_set(patterns)?;
+		Ok(self)
+	}
+
+	/// Resolves the inputs to a list of files
+	pub fn discover<I, S>(&self, inputs: I) -> DiscoveredFiles
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<str>,
+	{
+		let mut discovered = DiscoveredFiles::default();
+		let mut seen = HashSet::new();
+
+		for input in inputs {
+			let input = input.as_ref();
+			let path = Path::new(input);
+
+			let found = if path.is_file() {
+				vec![path.to_path_buf()]
+			} else if path.is_dir() {
+				self.filter(self.walk_directory(path, None))
+			} else if is_glob(input) {
+				let pattern = strip_current_dir(input);
+				match GlobBuilder::new(&pattern).literal_separator(true).build() {
+					Ok(glob) => self.filter(self.walk_glob(&pattern, &glob)),
+					Err(_) => vec![],
+				}
+			} else {
+				vec![]
+			};
+
+			if found.is_empty() {
+				discovered.unmatched.push(input.to_string());
+				continue;
+			}
+
+			for file in found {
+				if seen.insert(file.clone()) {
+					discovered.files.push(file);
+				}
+			}
+		}
+
+		discovered
+	}
+
+	/// Walks the directory that precedes the first glob segment and returns the files matching the glob
+	fn walk_glob(&self, input: &str, glob: &Glob) -> Vec<PathBuf> {
+		let mut base = PathBuf::new();
+		for component in Path::new(input).components() {
+			if let Component::Normal(segment) = component {
+				if is_glob(&segment.to_string_lossy()) {
+					break;
+				}
+			}
+			base.push(component);
+		}
+
+		let root = if base.as_os_str().is_empty() {
+			Path::new(".")
+		} else {
+			base.as_path()
+		};
+
+		if !root.is_dir() {
+			return vec![];
+		}
+
+		self.walk_directory(root, Some(&glob.compile_matcher()))
+	}
+
+	/// Recursively walks a directory, skipping the ignored paths, and returns all the files
+	/// that match the matcher, if any.
+	fn walk_directory(&self, directory: &Path, matcher: Option<&GlobMatcher>) -> Vec<PathBuf> {
+		WalkBuilder::new(directory)
+			.standard_filters(false)
+			.parents(true)
+			.add_custom_ignore_filename(IGNORE_FILE)
+			.git_ignore(self.gitignore)
+			// a `.gitignore` applies even if the project isn't inside a git repository
+			.require_git(false)
+			.filter_entry(|entry| entry.file_name() != ".git")
+			.sort_by_file_name(|a, b| a.cmp(b))
+			.build()
+			.filter_map(Result::ok)
+			.filter(|entry| matches!(entry.file_type(), Some(file_type) if file_type.is_file()))
+			.map(|entry| {
+				// walking "." prefixes every path with "./", which wouldn't match a glob like "*.js"
+				entry
+					.path()
+					.strip_prefix(".")
+					.map(Path::to_path_buf)
+					.unwrap_or_else(|_| entry.into_path())
+			})
+			.filter(|path| match matcher {
+				Some(matcher) => matcher.is_match(path),
+				None => true,
+			})
+			.collect()
+	}
+
+	/// Applies the include and ignore patterns to the files
+	fn filter(&self, files: Vec<PathBuf>) -> Vec<PathBuf> {
+		if self.include.is_none() && self.ignore.is_none() {
+			return files;
+		}
+
+		let current_dir = env::current_dir().unwrap_or_default();
+		files
+			.into_iter()
+			.filter(|file| {
+				let path = match &self.root {
+					Some(root) => match current_dir.join(file).strip_prefix(root) {
+						Ok(path) => path.to_path_buf(),
+						// the patterns don't apply to files outside of the root
+						Err(_) => return true,
+					},
+					None => file.clone(),
+				};
+
+				let included = match &self.include {
+					Some(include) => include.is_match(&path),
+					None => true,
+				};
+				let ignored = matches!(&self.ignore, Some(ignore) if ignore.is_match(&path));
+
+				included && !ignored
+			})
+			.collect()
+	}
+}
+
+/// Compiles the patterns to a single [GlobSet], returns [None] if there are no patterns
+fn build_glob_set<I, S>(patterns: I) -> Result<Option<GlobSet>, globset::Error>
+where
+	I: IntoIterator<Item = S>,
+	S: AsRef<str>,
+{
+	let mut builder = GlobSetBuilder::new();
+	let mut empty = true;
+	for pattern in patterns {
+		builder.add(
+			GlobBuilder::new(pattern.as_ref())
+				.literal_separator(true)
+				.build()?,
+		);
+		empty = false;
+	}
+
+	if empty {
+		Ok(None)
+	} else {
+		builder.build().map(Some)
+	}
+}
+
+/// Removes the leading `./` of a pattern, since the paths of the walked files don't have it
+fn strip_current_dir(pattern: &str) -> String {
+	let path: PathBuf = Path::new(pattern)
+		.components()
+		.skip_while(|component| component == &Component::CurDir)
+		.collect();
+	path.to_string_lossy().into_owned()
+}
+
+/// Returns `true` if the input contains any of the characters that have a special meaning
+/// inside a glob pattern
+fn is_glob(input: &str) -> bool {
+	input.contains(&['*', '?', '[', '{'][..])
+}
+
+#[cfg(test)]
+mod test {
+	use super::{discover_files, is_glob, strip_current_dir, FileDiscovery};
+	use std::env;
+	use std::fs;
+	use std::path::{Path, PathBuf};
+
+	/// A directory created in the temporary directory, removed when dropped
+	struct TestDirectory {
+		path: PathBuf,
+	}
+
+	impl TestDirectory {
+		fn new(name: &str, files: &[(&str, &str)]) -> Self {
+			let path = env::temp_dir().join(format!("rome_path_{}_{}", name, std::process::id()));
+			if path.exists() {
+				fs::remove_dir_all(&path).unwrap();
+			}
+			for (file, content) in files {
+				let file = path.join(file);
+				fs::create_dir_all(file.parent().unwrap()).unwrap();
+				fs::write(file, content).unwrap();
+			}
+			Self { path }
+		}
+
+		/// Returns the input that points to the path inside the directory
+		fn input(&self, path: &str) -> String {
+			self.path.join(path).to_str().unwrap().to_string()
+		}
+
+		/// Returns the paths of the files relative to the directory
+		fn relative(&self, files: &[PathBuf]) -> Vec<String> {
+			files
+				.iter()
+				.map(|file| file.strip_prefix(&self.path).unwrap())
+				.map(|file| file.to_str().unwrap().replace('\\', "/"))
+				.collect()
+		}
+	}
+
+	impl Drop for TestDirectory {
+		fn drop(&mut self) {
+			let _ = fs::remove_dir_all(&self.path);
+		}
+	}
+
+	#[test]
+	fn detects_glob_patterns() {
+		assert!(is_glob("src/**/*.js"));
+		assert!(is_glob("file.{js,ts}"));
+		assert!(!is_glob("src/lib.rs"));
+	}
+
+	#[test]
+	fn walks_directories() {
+		let directory = TestDirectory::new(
+			"walk",
+			&[
+				("src/b.js", ""),
+				("src/nested/c.js", ""),
+				("src/a.js", ""),
+				("d.js", ""),
+			],
+		);
+
+		let discovered = discover_files([directory.input("src")]);
+		assert_eq!(
+			directory.relative(&discovered.files),
+			vec!["src/a.js", "src/b.js", "src/nested/c.js"]
+		);
+		assert!(discovered.unmatched.is_empty());
+	}
+
+	#[test]
+	fn expands_globs() {
+		let directory = TestDirectory::new(
+			"glob",
+			&[
+				("src/a.rs", ""),
+				("src/b.js", ""),
+				("src/nested/c.rs", ""),
+				("Cargo.toml", ""),
+			],
+		);
+
+		let discovered = discover_files([directory.input("src/*.rs"), directory.input("*.toml")]);
+		assert_eq!(
+			directory.relative(&discovered.files),
+			vec!["src/a.rs", "Cargo.toml"]
+		);
+		assert!(discovered.unmatched.is_empty());
+	}
+
+	#[test]
+	fn strips_the_current_directory_from_patterns() {
+		assert_eq!(strip_current_dir("./src/*.rs"), "src/*.rs");
+		assert_eq!(strip_current_dir("*.toml"), "*.toml");
+		assert_eq!(
+			Path::new(&strip_current_dir("./src/./**/*.js")),
+			Path::new("src/**/*.js")
+		);
+	}
+
+	#[test]
+	fn deduplicates_files_and_reports_unmatched_inputs() {
+		let directory = TestDirectory::new("dedup", &[("src/a.js", ""), ("src/b.js", "")]);
+		let unknown = directory.input("**/*.unknown");
+
+		let discovered = discover_files([
+			directory.input("src/b.js"),
+			directory.input("src"),
+			unknown.clone(),
+		]);
+		assert_eq!(
+			directory.relative(&discovered.files),
+			vec!["src/b.js", "src/a.js"]
+		);
+		assert_eq!(discovered.unmatched, vec![unknown]);
+	}
+
+	#[test]
+	fn filters_walked_files_with_patterns() {
+		let directory = TestDirectory::new(
+			"filter",
+			&[("src/a.js", ""), ("src/b.js", ""), ("c.js", "")],
+		);
+
+		let discovered = FileDiscovery::default()
+			.with_root(&directory.path)
+			.with_include(["src/**"])
+			.unwrap()
+			.with_ignore(["**/b.js"])
+			.unwrap()
+			.discover([directory.input("."), directory.input("c.js")]);
+
+		// explicit inputs are never filtered
+		assert_eq!(
+			directory.relative(&discovered.files),
+			vec!["src/a.js", "c.js"]
+		);
+	}
+
+	#[test]
+	fn reports_invalid_patterns() {
+		let error = FileDiscovery::default()
+			.with_ignore(["src/[a"])
+			.unwrap_err();
+		assert_eq!(error.glob(), Some("src/[a"));
+	}
+
+	#[test]
+	fn skips_ignored_files() {
+		let directory = TestDirectory::new(
+			"ignore",
+			&[
+				(".romeignore", "dist/\n*.min.js\n!keep.min.js\n"),
+				(".gitignore", "generated.js\n"),
+				("a.js", ""),
+				("a.min.js", ""),
+				("keep.min.js", ""),
+				("generated.js", ""),
+				("dist/b.js", ""),
+				("src/dist", ""),
+				("src/c.js", ""),
+				("src/.romeignore", "c.js\n"),
+				("vendor/d.js", ""),
+			],
+		);
+
+		let relative = |discovery: FileDiscovery| -> Vec<String> {
+			let discovered = discovery.discover([directory.input(".")]);
+			directory
+				.relative(&discovered.files)
+				.into_iter()
+				.filter(|file| file.ends_with(".js") || file.ends_with("dist"))
+				.collect()
+		};
+
+		assert_eq!(
+			relative(FileDiscovery::default()),
+			vec![
+				"a.js",
+				"generated.js",
+				"keep.min.js",
+				"src/dist",
+				"vendor/d.js"
+			]
+		);
+		assert_eq!(
+			relative(FileDiscovery::default().with_gitignore(true)),
+			vec!["a.js", "keep.min.js", "src/dist", "vendor/d.js"]
+		);
+	}
+}
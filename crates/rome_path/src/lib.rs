@@ -1,10 +1,16 @@
 //! This module is responsible to manage paths inside Rome.
 //! It is a small wrapper around [path::PathBuf] but it is also able to
 //! give additional information around the the file that holds:
-//! - the [FileHandlers] for the specific file
-//! - shortcuts to open/write to the file
-use rome_core::{file_handlers::ExtensionHandler, App};
-use std::{fs::File, io::Write, ops::Deref, path::PathBuf};
+//! - the [ExtensionHandler] for the specific file
+//! - shortcuts to read/write the file through a [FileSystem]
+//!
+//! It also contains the [discover_files] function, used to find the files to process.
+use rome_core::{file_handlers::ExtensionHandler, workspace::FileSystem, App};
+use std::{io, ops::Deref, path::PathBuf};
+
+mod discovery;
+
+pub use discovery::{discover_files, DiscoveredFiles, FileDiscovery, IGNORE_FILE};
 
 pub struct RomePath<'handler> {
 	file: PathBuf,
@@ -20,14 +26,14 @@ impl<'handler> Deref for RomePath<'handler> {
 }
 
 impl<'handler> RomePath<'handler> {
-	pub fn new(path_to_file: &str) -> Self {
+	pub fn new<P: Into<PathBuf>>(path_to_file: P) -> Self {
 		Self {
-			file: PathBuf::from(path_to_file),
+			file: path_to_file.into(),
 			handler: None,
 		}
 	}
 
-	/// Deduce the file handler based on the extension of the file.
+	/// Deduce the file handler based on the name of the file, and then on its extension.
 	///
 	/// Any error will default to the base file handler for now.
 	///
@@ -42,7 +48,7 @@ impl<'handler> RomePath<'handler> {
 	/// let app = create_app();
 	/// let file = RomePath::new("file.js").deduce_handler(&app);
 	/// let handler = file.get_handler();
-	/// let expected = JsFileHandler {};
+	/// let expected = JsFileHandler::default();
 	/// assert_eq!(
 	///   handler.unwrap().capabilities().format,
 	///   expected.capabilities().format
@@ -53,32 +59,30 @@ impl<'handler> RomePath<'handler> {
 	/// )
 	/// ```
 	pub fn deduce_handler(mut self, app: &'handler App) -> Self {
-		if self.extension().is_none() {
-			return self;
-		}
-		let extension = self.extension().unwrap().to_str().unwrap();
+		self.handler = app.get_handler_by_path(&self.file);
+		self
+	}
 
-		if let Some(handler) = app.get_handler(extension) {
-			self.handler = Some(handler);
-		} else {
-			// we know that unknown is hardcoded
-			self.handler = Some(app.get_handler("unknown").unwrap());
+	/// Deduce the file handler from the content of the file, when its name and its extension
+	/// didn't give one. See [App::get_handler_by_content].
+	pub fn deduce_handler_from_content(mut self, app: &'handler App, content: &str) -> Self {
+		if self.handler.is_none() {
+			self.handler = app.get_handler_by_content(&self.file, content);
 		}
-
 		self
 	}
 
-	// TODO: handle error with diagnostic?
-	/// Opens a file and returns a [File] in write mode
-	pub fn open(&self) -> File {
-		File::open(&self.file).expect("cannot open the file to format")
+	/// Reads the content of the file from the file system
+	pub fn read(&self, file_system: &dyn FileSystem) -> io::Result<String> {
+		file_system.read(&self.file)
 	}
 
-	/// Accepts a file opened in read mode and saves into it
-	pub fn save(&mut self, content: &str) -> Result<(), std::io::Error> {
-		let mut file_to_write = File::create(&self.file).unwrap();
-		// TODO: handle error with diagnostic
-		file_to_write.write_all(content.as_bytes())
+	/// Replaces the content of the file on the file system.
+	///
+	/// With the [rome_core::workspace::OsFileSystem], the content is written to a temporary file
+	/// that is then renamed to the file, and the file isn't written if its content is unchanged.
+	pub fn save(&mut self, file_system: &dyn FileSystem, content: &str) -> io::Result<()> {
+		file_system.write(&self.file, content)
 	}
 
 	/// Returns the current handler associated to the file.
@@ -96,14 +100,16 @@ mod test {
 	use rome_core::{
 		create_app,
 		file_handlers::{javascript::JsFileHandler, ExtensionHandler},
+		workspace::{FileSystem, MemoryFileSystem},
 	};
+	use std::path::Path;
 
 	#[test]
 	fn deduce_handler() {
 		let app = create_app();
 		let file = RomePath::new("file.js").deduce_handler(&app);
 		let handler = file.get_handler();
-		let expected = JsFileHandler {};
+		let expected = JsFileHandler::default();
 		assert_eq!(
 			handler.unwrap().capabilities().format,
 			expected.capabilities().format
@@ -113,4 +119,19 @@ mod test {
 			expected.capabilities().lint
 		)
 	}
+
+	#[test]
+	fn reads_and_saves_through_the_file_system() {
+		let file_system = MemoryFileSystem::default();
+		file_system.insert("file.js", "let a =   1");
+
+		let mut file = RomePath::new("file.js");
+		assert_eq!(file.read(&file_system).unwrap(), "let a =   1");
+		file.save(&file_system, "let a = 1;\n").unwrap();
+		assert_eq!(
+			file_system.read(Path::new("file.js")).unwrap(),
+			"let a = 1;\n"
+		);
+		assert!(RomePath::new("other.js").read(&file_system).is_err());
+	}
 }
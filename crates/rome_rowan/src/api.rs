@@ -128,7 +128,7 @@ impl<L: Language> SyntaxTriviaPiece<L> {
 	/// ```
 	pub fn text(&self) -> &str {
 		let txt = self.raw.text();
-		let start = self.offset - self.raw.offset();
+		let start = self.offset - self.raw.text_range().start();
 		let end = start + self.text_len();
 
 		&txt[start.into()..end.into()]
@@ -1668,5 +1668,11 @@ mod tests {
 		assert_eq!(TextSize::from(4), pieces[1].text_len());
 		assert_eq!(TextRange::new(3.into(), 7.into()), pieces[1].text_range());
 		assert!(pieces[1].as_comments().is_some());
+
+		let pieces: Vec<_> = node.last_trailing_trivia().unwrap().pieces().collect();
+		assert_eq!(1, pieces.len());
+
+		assert_eq!(" \t\t", pieces[0].text());
+		assert_eq!(TextRange::new(10.into(), 13.into()), pieces[0].text_range());
 	}
 }
@@ -619,14 +619,9 @@ impl SyntaxTrivia {
 		SyntaxTriviaPiecesIterator {
 			raw: self.clone(),
 			next_index: 0,
-			next_offset: self.offset,
+			next_offset: self.text_range().start(),
 		}
 	}
-
-	#[inline]
-	pub(crate) fn offset(&self) -> TextSize {
-		self.offset
-	}
 }
 
 impl SyntaxNode {
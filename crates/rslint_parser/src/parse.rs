@@ -1,6 +1,6 @@
 //! Utilities for high level parsing of js code.
 
-use crate::ast::{JsModule, JsScript};
+use crate::ast::{JsAnyRoot, JsModule, JsScript};
 use crate::{ast::JsAnyExpression, *};
 use rslint_errors::Severity;
 use std::marker::PhantomData;
@@ -231,6 +231,28 @@ pub fn parse_module(text: &str, file_id: usize) -> Parse<JsModule> {
 	Parse::new_module(green, parse_errors)
 }
 
+/// Losslessly parse text into a [`Parse`](Parse) using the given [`Syntax`](Syntax).
+///
+/// The root is a script if the file kind of the syntax is [`FileKind::Script`](FileKind::Script), a module otherwise.
+///
+/// ```
+/// use rslint_parser::{parse, FileKind, JsSourceType, Syntax};
+///
+/// let parse = parse("export let a = 1;", 0, Syntax::new(FileKind::Module));
+/// assert_eq!(parse.source_type(), JsSourceType::Module);
+/// assert!(parse.errors().is_empty());
+/// ```
+pub fn parse(text: &str, file_id: usize, syntax: Syntax) -> Parse<JsAnyRoot> {
+	let (events, errors, tokens) = parse_common(text, file_id, syntax);
+	let mut tree_sink = LosslessTreeSink::new(text, &tokens);
+	crate::process(&mut tree_sink, events, errors);
+	let (green, parse_errors) = tree_sink.finish();
+	match syntax.file_kind {
+		FileKind::Script => Parse::new_script(green, parse_errors),
+		FileKind::Module | FileKind::TypeScript => Parse::new_module(green, parse_errors),
+	}
+}
+
 /// Losslessly Parse text into an expression [`Parse`](Parse) which can then be turned into an untyped root [`SyntaxNode`](SyntaxNode).
 /// Or turned into a typed [`Expr`](Expr) with [`tree`](Parse::tree).
 pub fn parse_expr(text: &str, file_id: usize) -> Parse<JsAnyExpression> {
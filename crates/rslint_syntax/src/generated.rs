@@ -415,6 +415,84 @@ impl SyntaxKind {
 			_ => false,
 		}
 	}
+	#[doc = r" Returns `true` for the nodes of the TypeScript syntax, like a type annotation"]
+	pub fn is_ts(self) -> bool {
+		match self {
+			TS_TYPE_ANNOTATION
+			| TS_ANY
+			| TS_UNKNOWN
+			| TS_NUMBER
+			| TS_OBJECT
+			| TS_BOOLEAN
+			| TS_BIGINT
+			| TS_STRING
+			| TS_SYMBOL
+			| TS_VOID
+			| TS_UNDEFINED
+			| TS_NULL
+			| TS_NEVER
+			| TS_THIS
+			| TS_LITERAL
+			| TS_PREDICATE
+			| TS_TUPLE
+			| TS_TUPLE_ELEMENT
+			| TS_PAREN
+			| TS_TYPE_REF
+			| TS_QUALIFIED_PATH
+			| TS_TYPE_NAME
+			| TS_TEMPLATE
+			| TS_TEMPLATE_ELEMENT
+			| TS_MAPPED_TYPE
+			| TS_MAPPED_TYPE_PARAM
+			| TS_MAPPED_TYPE_READONLY
+			| TS_TYPE_QUERY
+			| TS_TYPE_QUERY_EXPR
+			| TS_IMPORT
+			| TS_TYPE_ARGS
+			| TS_ARRAY
+			| TS_INDEXED_ARRAY
+			| TS_TYPE_OPERATOR
+			| TS_INTERSECTION
+			| TS_UNION
+			| TS_TYPE_PARAMS
+			| TS_FN_TYPE
+			| TS_CONSTRUCTOR_TYPE
+			| TS_IMPLEMENTS_CLAUSE
+			| TS_EXTENDS
+			| TS_CONDITIONAL_TYPE
+			| TS_CONSTRAINT
+			| TS_DEFAULT
+			| TS_TYPE_PARAM
+			| TS_NON_NULL
+			| TS_ASSERTION
+			| TS_CONST_ASSERTION
+			| TS_ENUM
+			| TS_ENUM_MEMBER
+			| TS_TYPE_ALIAS_DECL
+			| TS_NAMESPACE_DECL
+			| TS_MODULE_BLOCK
+			| TS_MODULE_DECL
+			| TS_CONSTRUCTOR_PARAM
+			| TS_CALL_SIGNATURE_DECL
+			| TS_CONSTRUCT_SIGNATURE_DECL
+			| TS_INDEX_SIGNATURE
+			| TS_METHOD_SIGNATURE
+			| TS_PROPERTY_SIGNATURE
+			| TS_INTERFACE_DECL
+			| TS_ACCESSIBILITY
+			| TS_OBJECT_TYPE
+			| TS_EXPR_WITH_TYPE_ARGS
+			| TS_IMPORT_EQUALS_DECL
+			| TS_MODULE_REF
+			| TS_EXTERNAL_MODULE_REF
+			| TS_EXPORT_ASSIGNMENT
+			| TS_NAMESPACE_EXPORT_DECL
+			| TS_DECORATOR
+			| TS_INFER
+			| TS_ENTITY_NAME => true,
+			_ => false,
+		}
+	}
 	pub fn is_before_expr(self) -> bool {
 		match self {
 			BANG | L_PAREN | L_BRACK | L_CURLY | SEMICOLON | COMMA | COLON | QUESTION | PLUS2
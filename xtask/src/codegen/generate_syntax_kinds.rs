@@ -62,6 +62,13 @@ pub fn generate_syntax_kinds(grammar: KindsSrc) -> Result<String> {
 		.map(|name| format_ident!("{}", name))
 		.collect::<Vec<_>>();
 
+	let ts_nodes = grammar
+		.nodes
+		.iter()
+		.filter(|name| name.starts_with("TS_"))
+		.map(|name| format_ident!("{}", name))
+		.collect::<Vec<_>>();
+
 	let ast = quote! {
 		#![allow(clippy::all)]
 		#![allow(bad_style, missing_docs, unreachable_pub)]
@@ -110,6 +117,14 @@ pub fn generate_syntax_kinds(grammar: KindsSrc) -> Result<String> {
 				}
 			}
 
+			/// Returns `true` for the nodes of the TypeScript syntax, like a type annotation
+			pub fn is_ts(self) -> bool {
+				match self {
+					#(#ts_nodes)|* => true,
+					_ => false,
+				}
+			}
+
 			pub fn is_before_expr(self) -> bool {
 				match self {
 					BANG | L_PAREN | L_BRACK | L_CURLY | SEMICOLON | COMMA | COLON